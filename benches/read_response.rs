@@ -0,0 +1,130 @@
+//! Measures the CPU time spent reading responses, including while waiting for a response which has
+//! not arrived yet, and compares it with the reader used before responses were read with `BufRead`.
+//! Run with `cargo bench --bench read_response`.
+
+use std::collections::VecDeque;
+use std::io::{Error, Read, Write};
+use std::time::{Duration, Instant};
+
+use cpu_time::ThreadTime;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use odrive_rs::prelude::*;
+
+const RESPONSE: &[u8] = b"-1234.5678\n";
+
+/// An in-memory stream, like the `MockStream` used by the tests, which returns its data in chunks
+/// of at most `chunk` bytes, and no data once it is empty.
+struct ChunkedStream {
+    data: VecDeque<u8>,
+    chunk: usize,
+}
+
+impl ChunkedStream {
+    fn new(data: Vec<u8>, chunk: usize) -> Self {
+        Self { data: data.into(), chunk }
+    }
+}
+
+impl Read for ChunkedStream {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        let len = buf.len().min(self.chunk).min(self.data.len());
+        for (byte, data) in buf.iter_mut().zip(self.data.drain(..len)) {
+            *byte = data;
+        }
+        Ok(len)
+    }
+}
+
+impl Write for ChunkedStream {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// A copy of the previous `ODrive::read_odrive_response`, which read one byte at a time through
+/// the `Read` implementation of the `ODrive`, and polled the stream without sleeping. The timeout
+/// was fixed at one second, and is a parameter here.
+fn baseline_read_response<R: Read>(reader: &mut R, timeout: Duration) -> Option<String> {
+    let mut string = String::with_capacity(20);
+    let duration = Instant::now();
+    loop {
+        let mut buffer = [0; 1];
+        while reader.read(&mut buffer).unwrap_or_default() == 0 {
+            if duration.elapsed() >= timeout {
+                return None;
+            }
+        }
+        let ch = buffer[0];
+        if ch as char == '\n' {
+            break;
+        }
+
+        string.push(ch as char);
+    }
+
+    Some(string.trim().to_owned())
+}
+
+/// Reads `iterations` responses from a stream returning `chunk` bytes at a time, and returns the
+/// CPU time taken.
+fn read_responses(iterations: u64, chunk: usize, baseline: bool) -> Duration {
+    let data = RESPONSE.repeat(iterations as usize);
+    let mut odrive = ODrive::new(ChunkedStream::new(data, chunk));
+    let start = ThreadTime::now();
+    for _ in 0..iterations {
+        if baseline {
+            baseline_read_response(&mut odrive, Duration::from_secs(1)).unwrap();
+        } else {
+            odrive.read_odrive_response().unwrap();
+        }
+    }
+    start.elapsed()
+}
+
+fn bench_read_response(c: &mut Criterion) {
+    let mut group = c.benchmark_group("read_response_cpu_time");
+    group.bench_function("buffered", |b| b.iter_custom(|iterations| read_responses(iterations, usize::MAX, false)));
+    group.bench_function("buffered_baseline", |b| {
+        b.iter_custom(|iterations| read_responses(iterations, usize::MAX, true))
+    });
+    group.bench_function("one_byte_at_a_time", |b| b.iter_custom(|iterations| read_responses(iterations, 1, false)));
+    group.bench_function("one_byte_at_a_time_baseline", |b| {
+        b.iter_custom(|iterations| read_responses(iterations, 1, true))
+    });
+    group.finish();
+}
+
+/// Waits for a response which never arrives. The CPU time should stay far below the read timeout,
+/// as the stream is polled with a sleep rather than in a busy loop, while the baseline spends the
+/// whole timeout on the CPU.
+fn bench_wait_for_missing_response(c: &mut Criterion) {
+    let timeout = Duration::from_millis(20);
+    let mut group = c.benchmark_group("missing_response_cpu_time");
+    group.sample_size(10);
+    group.bench_function("20ms_timeout", |b| b.iter_custom(|iterations| {
+        let mut odrive = ODrive::new(ChunkedStream::new(Vec::new(), usize::MAX));
+        odrive.set_timeouts(Timeouts { read: timeout, ..Timeouts::default() });
+        let start = ThreadTime::now();
+        for _ in 0..iterations {
+            assert!(odrive.read_odrive_response().is_err());
+        }
+        start.elapsed()
+    }));
+    group.bench_function("20ms_timeout_baseline", |b| b.iter_custom(|iterations| {
+        let mut odrive = ODrive::new(ChunkedStream::new(Vec::new(), usize::MAX));
+        let start = ThreadTime::now();
+        for _ in 0..iterations {
+            assert!(baseline_read_response(&mut odrive, timeout).is_none());
+        }
+        start.elapsed()
+    }));
+    group.finish();
+}
+
+criterion_group!(benches, bench_read_response, bench_wait_for_missing_response);
+criterion_main!(benches);
@@ -0,0 +1,532 @@
+//! Generates the typed property tree from the interface definitions in `interface/`.
+//!
+//! Every `interface/odrive-interface-<version>.yaml` becomes a module `fw_<version>` of
+//! `crate::properties`, holding one struct per object of the object model and one enum per
+//! enumeration or set of error flags. The generated code is made of invocations of the macros
+//! defined in `src/properties/mod.rs`.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use yaml_rust::{Yaml, YamlLoader};
+
+const INTERFACE_DIR: &str = "interface";
+const FILE_PREFIX: &str = "odrive-interface-";
+const FILE_SUFFIX: &str = ".yaml";
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed={}", INTERFACE_DIR);
+    // Set by cargo-tarpaulin, which the crate uses to skip code from coverage.
+    println!("cargo:rustc-check-cfg=cfg(tarpaulin)");
+
+    let mut files: Vec<_> = fs::read_dir(INTERFACE_DIR)
+        .expect("could not read the interface directory")
+        .map(|entry| entry.expect("could not read the interface directory").path())
+        .filter(|path| version_of(path).is_some())
+        .collect();
+    files.sort();
+
+    let mut out = String::new();
+    for path in files {
+        println!("cargo:rerun-if-changed={}", path.display());
+        let source = fs::read_to_string(&path).expect("could not read an interface file");
+        let documents = YamlLoader::load_from_str(&source)
+            .unwrap_or_else(|error| panic!("{}: {}", path.display(), error));
+        let version = version_of(&path).unwrap();
+        let file_name = path.file_name().unwrap().to_string_lossy();
+        generate_module(&mut out, &version, &file_name, &documents[0]);
+    }
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("interfaces.rs");
+    fs::write(out_path, out).expect("could not write the generated property tree");
+}
+
+/// Extracts the firmware version from a file name such as `odrive-interface-0.5.yaml`.
+fn version_of(path: &Path) -> Option<String> {
+    let name = path.file_name()?.to_str()?;
+    let version = name.strip_prefix(FILE_PREFIX)?.strip_suffix(FILE_SUFFIX)?;
+    if !version.is_empty() && version.chars().all(|c| c.is_ascii_digit() || c == '.') {
+        Some(version.to_owned())
+    } else {
+        None
+    }
+}
+
+enum ValueKind {
+    Enum,
+    Flags,
+}
+
+struct ValueType {
+    name: String,
+    kind: ValueKind,
+    doc: Option<String>,
+    /// The Rust name, raw value and documentation of every variant.
+    variants: Vec<(String, u64, Option<String>)>,
+}
+
+impl ValueType {
+    /// The Rust type of a property holding this value type.
+    fn property_type(&self) -> String {
+        match self.kind {
+            ValueKind::Enum => self.name.clone(),
+            ValueKind::Flags => format!("{}s", self.name),
+        }
+    }
+}
+
+enum MemberKind {
+    Object(String),
+    Property { ty: String, read_only: bool },
+}
+
+struct Member {
+    name: String,
+    doc: Option<String>,
+    kind: MemberKind,
+}
+
+struct Object {
+    name: String,
+    doc: String,
+    members: Vec<Member>,
+}
+
+/// Holds the definitions of a single interface file while it is translated.
+struct Model<'a> {
+    interfaces: BTreeMap<String, &'a Yaml>,
+    /// The Rust name of every interface, by its full name.
+    names: BTreeMap<String, String>,
+    /// Every Rust type name given out so far, so that no two types share one.
+    used_names: BTreeSet<String>,
+    value_types: BTreeMap<String, ValueType>,
+    objects: Vec<Object>,
+}
+
+fn generate_module(out: &mut String, version: &str, file_name: &str, document: &Yaml) {
+    let mut model = Model {
+        interfaces: BTreeMap::new(),
+        names: BTreeMap::new(),
+        used_names: BTreeSet::new(),
+        value_types: BTreeMap::new(),
+        objects: Vec::new(),
+    };
+
+    // Interfaces whose names are not plain dotted identifiers, such as generic Fibre types, are
+    // skipped, along with the members using them.
+    let interfaces: Vec<_> = entries(&document["interfaces"]).into_iter()
+        .filter(|(name, _)| name.split('.').all(is_identifier))
+        .collect();
+    for (name, definition) in &interfaces {
+        model.interfaces.insert(name.clone(), *definition);
+        let rust_name = model.allocate_name(object_name(name), name);
+        model.names.insert(name.clone(), rust_name);
+    }
+
+    // Value types can be declared at the top level or inline, next to the attribute using them.
+    for (name, definition) in entries(&document["valuetypes"]) {
+        model.add_value_type(&name, definition);
+    }
+    for (name, definition) in &interfaces {
+        model.collect_inline_value_types(name, definition);
+    }
+
+    for (name, definition) in &interfaces {
+        let rust_name = model.names[name].clone();
+        model.add_object(name, rust_name, None, definition);
+    }
+
+    let module = format!("fw_{}", version.replace('.', "_"));
+    writeln!(out, "#[doc = \"The object model of firmware {}, generated from `interface/{}`.\"]", version, file_name).unwrap();
+    writeln!(out, "pub mod {} {{", module).unwrap();
+    writeln!(out, "    use std::io::Read;\n").unwrap();
+    writeln!(out, "    use crate::commands::ODrive;").unwrap();
+    writeln!(out, "    use crate::enumerations::AxisID;").unwrap();
+    writeln!(out, "    #[allow(unused_imports)]").unwrap();
+    writeln!(out, "    use crate::enumerations::errors::ErrorSet;").unwrap();
+    writeln!(out, "    #[allow(unused_imports)]").unwrap();
+    writeln!(out, "    use crate::properties::{{ConfigProperties, Property, ReadOnly, ReadWrite, ValueKind}};\n").unwrap();
+
+    for value_type in model.value_types.values() {
+        model.write_value_type(out, value_type);
+    }
+    for object in &model.objects {
+        write_object(out, object);
+    }
+    model.write_roots(out);
+    model.write_config_properties(out);
+
+    writeln!(out, "}}\n").unwrap();
+}
+
+impl<'a> Model<'a> {
+    /// Gives out `preferred` as a type name if it is free, or otherwise a name qualified with
+    /// every part of `full_name`, such as `MotorConfig` for `ODrive.Motor.Config`.
+    fn allocate_name(&mut self, preferred: String, full_name: &str) -> String {
+        let qualified: String = full_name.split('.')
+            .filter(|part| *part != "ODrive")
+            .map(camel_case)
+            .collect();
+        let mut candidates = vec![preferred, qualified.clone()];
+        candidates.extend((2..).map(|suffix| format!("{}{}", qualified, suffix)).take(100));
+        let name = candidates.into_iter()
+            .find(|name| is_identifier(name) && !self.used_names.contains(name))
+            .unwrap_or_else(|| panic!("could not name `{}`", full_name));
+        self.used_names.insert(name.clone());
+        name
+    }
+
+    fn add_value_type(&mut self, full_name: &str, definition: &Yaml) {
+        let (kind, entries) = if !definition["flags"].is_badvalue() {
+            (ValueKind::Flags, entries(&definition["flags"]))
+        } else if !definition["values"].is_badvalue() {
+            (ValueKind::Enum, entries(&definition["values"]))
+        } else {
+            return;
+        };
+
+        let mut variants: Vec<(String, u64, Option<String>)> = Vec::new();
+        let mut next = 0;
+        for (name, entry) in entries {
+            let value = match kind {
+                ValueKind::Enum => integer(&entry["value"]).unwrap_or(next),
+                ValueKind::Flags => match integer(&entry["bit"]).unwrap_or(next) {
+                    bit if bit < 64 => 1 << bit,
+                    _ => continue,
+                },
+            };
+            next = match kind {
+                ValueKind::Enum => value + 1,
+                ValueKind::Flags => u64::from(value.trailing_zeros()) + 1,
+            };
+
+            // Variants named with a leading digit are prefixed to make them identifiers.
+            let mut variant = camel_case(&name);
+            if variant.starts_with(|c: char| c.is_ascii_digit()) {
+                variant.insert(0, 'V');
+            }
+            let duplicate = variants.iter().any(|(existing, existing_value, _)| *existing == variant || *existing_value == value);
+            if is_identifier(&variant) && !duplicate {
+                variants.push((variant, value, doc(entry)));
+            }
+        }
+
+        // An empty set of flags is better represented by its raw value.
+        if variants.is_empty() {
+            return;
+        }
+
+        if self.value_types.contains_key(full_name) {
+            return;
+        }
+        let name = self.allocate_name(value_type_name(full_name), full_name);
+        self.value_types.insert(full_name.to_owned(), ValueType {
+            name,
+            kind,
+            doc: doc(definition),
+            variants,
+        });
+    }
+
+    fn collect_inline_value_types(&mut self, owner: &str, definition: &Yaml) {
+        for (name, attribute) in entries(&definition["attributes"]) {
+            let full_name = format!("{}.{}", owner, camel_case(&name));
+            if !attribute["attributes"].is_badvalue() {
+                self.collect_inline_value_types(&full_name, attribute);
+            } else {
+                self.add_value_type(&full_name, attribute);
+            }
+        }
+    }
+
+    /// Translates an interface, or an object declared inline, and every inline object below it.
+    fn add_object(&mut self, full_name: &str, name: String, doc_override: Option<String>, definition: &Yaml) {
+        let object_doc = doc_override
+            .or_else(|| doc(definition))
+            .unwrap_or_else(|| format!("The `{}` object.", full_name));
+        let mut object = Object { name: name.clone(), doc: object_doc, members: Vec::new() };
+        let mut inline = Vec::new();
+
+        for (attribute_name, attribute) in entries(&definition["attributes"]) {
+            if !is_identifier(&attribute_name) {
+                continue;
+            }
+            let member_full_name = format!("{}.{}", full_name, camel_case(&attribute_name));
+            let kind = if !attribute["attributes"].is_badvalue() {
+                let child = self.allocate_name(format!("{}{}", name, camel_case(&attribute_name)), &member_full_name);
+                inline.push((member_full_name, attribute_name.clone(), child.clone(), attribute));
+                Some(MemberKind::Object(child))
+            } else if let Some(value_type) = self.value_types.get(&member_full_name) {
+                Some(MemberKind::Property { ty: value_type.property_type(), read_only: false })
+            } else if let Some(spec) = attribute.as_str().or_else(|| attribute["type"].as_str()) {
+                self.member_kind(spec)
+            } else {
+                None
+            };
+
+            // Members which cannot be represented, such as endpoint references, are left out.
+            if let Some(kind) = kind {
+                object.members.push(Member { name: attribute_name, doc: doc(attribute), kind });
+            }
+        }
+
+        self.objects.push(object);
+        for (member_full_name, attribute_name, child, attribute) in inline {
+            let doc = format!("The `{}` member of [`{}`].", attribute_name, name);
+            self.add_object(&member_full_name, child, Some(doc), attribute);
+        }
+    }
+
+    /// Resolves a type specification such as `readonly float32` or `ODrive.Axis`.
+    fn member_kind(&self, spec: &str) -> Option<MemberKind> {
+        let read_only = spec.split_whitespace().any(|word| word == "readonly");
+        let type_name = spec.split_whitespace().last()?;
+
+        if let Some(name) = self.names.get(type_name) {
+            return Some(MemberKind::Object(name.clone()));
+        }
+        let ty = match type_name {
+            "bool" => "bool".to_owned(),
+            "float32" => "f32".to_owned(),
+            "uint8" => "u8".to_owned(),
+            "uint16" => "u16".to_owned(),
+            "uint32" => "u32".to_owned(),
+            "uint64" => "u64".to_owned(),
+            "int8" => "i8".to_owned(),
+            "int16" => "i16".to_owned(),
+            "int32" => "i32".to_owned(),
+            "int64" => "i64".to_owned(),
+            other => self.value_types.get(other)?.property_type(),
+        };
+        Some(MemberKind::Property { ty, read_only })
+    }
+
+    fn write_value_type(&self, out: &mut String, value_type: &ValueType) {
+        let max = value_type.variants.iter().map(|(_, value, _)| *value).max().unwrap_or(0);
+        match value_type.kind {
+            ValueKind::Enum => {
+                let repr = if max <= u64::from(u8::MAX) {
+                    "u8"
+                } else if max <= u64::from(u16::MAX) {
+                    "u16"
+                } else {
+                    "u32"
+                };
+                writeln!(out, "    property_enum! {{").unwrap();
+                write_doc(out, 8, value_type.doc.as_deref());
+                writeln!(out, "        {}: {} {{", value_type.name, repr).unwrap();
+                for (name, value, doc) in &value_type.variants {
+                    write_doc(out, 12, doc.as_deref());
+                    writeln!(out, "            {} = {},", name, value).unwrap();
+                }
+            }
+            ValueKind::Flags => {
+                writeln!(out, "    #[doc = \"A set of [`{}`] flags.\"]", value_type.name).unwrap();
+                writeln!(out, "    pub type {} = ErrorSet<{}>;\n", value_type.property_type(), value_type.name).unwrap();
+                writeln!(out, "    property_flags! {{").unwrap();
+                write_doc(out, 8, value_type.doc.as_deref());
+                writeln!(out, "        {} {{", value_type.name).unwrap();
+                for (name, value, doc) in &value_type.variants {
+                    write_doc(out, 12, doc.as_deref());
+                    writeln!(out, "            {} = {:#x},", name, value).unwrap();
+                }
+            }
+        }
+        writeln!(out, "        }}\n    }}\n").unwrap();
+    }
+
+    /// Writes the entry points of the tree: the board, and its axes.
+    fn write_roots(&self, out: &mut String) {
+        if self.find_object("Board").is_none() {
+            return;
+        }
+
+        writeln!(out, "    /// The root of the object model, holding the board level properties.").unwrap();
+        writeln!(out, "    pub fn board<T: Read>(odrive: &mut ODrive<T>) -> Board<'_, T> {{").unwrap();
+        writeln!(out, "        Board::new(odrive, String::new())\n    }}\n").unwrap();
+
+        if let Some(axis) = self.axis_type() {
+            writeln!(out, "    /// One of the axes of the object model.").unwrap();
+            writeln!(out, "    pub fn axis<T: Read>(odrive: &mut ODrive<T>, axis: AxisID) -> {}<'_, T> {{", axis).unwrap();
+            writeln!(out, "        {}::new(odrive, format!(\"axis{{}}\", axis as u8))\n    }}", axis).unwrap();
+        }
+    }
+
+    fn find_object(&self, name: &str) -> Option<&Object> {
+        self.objects.iter().find(|object| object.name == name)
+    }
+
+    /// The type of the axes of the board, taken from `axis0`.
+    fn axis_type(&self) -> Option<&String> {
+        self.find_object("Board")?.members.iter().find_map(|member| match &member.kind {
+            MemberKind::Object(ty) if member.name == "axis0" => Some(ty),
+            _ => None,
+        })
+    }
+
+    /// Writes the paths of the configurable properties of the board and of an axis, which are the
+    /// writable properties below a `config` object.
+    fn write_config_properties(&self, out: &mut String) {
+        let axis = match self.axis_type() {
+            Some(axis) => axis,
+            None => return,
+        };
+
+        let mut board = Vec::new();
+        self.collect_config_properties("Board", "", false, Some(axis), &mut board);
+        writeln!(out, "\n    /// The configurable properties of the board, not including its axes.").unwrap();
+        write_config_list(out, "BOARD_CONFIG", &board);
+
+        let mut axis_config = Vec::new();
+        self.collect_config_properties(axis, "", false, None, &mut axis_config);
+        writeln!(out, "    /// The configurable properties of an axis, relative to the axis.").unwrap();
+        write_config_list(out, "AXIS_CONFIG", &axis_config);
+    }
+
+    fn collect_config_properties(&self, object: &str, path: &str, in_config: bool, skip: Option<&String>,
+                                 out: &mut Vec<(String, &'static str)>) {
+        let object = match self.find_object(object) {
+            Some(object) => object,
+            None => return,
+        };
+        for member in &object.members {
+            let member_path = if path.is_empty() { member.name.clone() } else { format!("{}.{}", path, member.name) };
+            match &member.kind {
+                MemberKind::Object(ty) if Some(ty) != skip => {
+                    let in_config = in_config || member.name == "config";
+                    self.collect_config_properties(ty, &member_path, in_config, skip, out);
+                }
+                MemberKind::Property { ty, read_only: false } if in_config => {
+                    let kind = match ty.as_str() {
+                        "bool" => "Bool",
+                        "f32" => "Float",
+                        // Enumerations and error flags are stored as their raw value.
+                        _ => "Integer",
+                    };
+                    out.push((member_path, kind));
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn write_config_list(out: &mut String, name: &str, properties: &[(String, &str)]) {
+    writeln!(out, "    pub const {}: ConfigProperties = &[", name).unwrap();
+    for (path, kind) in properties {
+        writeln!(out, "        ({:?}, ValueKind::{}),", path, kind).unwrap();
+    }
+    writeln!(out, "    ];\n").unwrap();
+}
+
+fn write_object(out: &mut String, object: &Object) {
+    writeln!(out, "    property_object! {{").unwrap();
+    write_doc(out, 8, Some(&object.doc));
+    writeln!(out, "        {} {{", object.name).unwrap();
+
+    writeln!(out, "            objects {{").unwrap();
+    for member in &object.members {
+        if let MemberKind::Object(ty) = &member.kind {
+            write_doc(out, 16, member.doc.as_deref());
+            writeln!(out, "                {}: {},", member_ident(&member.name), ty).unwrap();
+        }
+    }
+    writeln!(out, "            }}").unwrap();
+
+    writeln!(out, "            properties {{").unwrap();
+    for member in &object.members {
+        if let MemberKind::Property { ty, read_only } = &member.kind {
+            let access = if *read_only { "ReadOnly" } else { "ReadWrite" };
+            write_doc(out, 16, member.doc.as_deref());
+            writeln!(out, "                {}: {}, {};", member_ident(&member.name), ty, access).unwrap();
+        }
+    }
+    writeln!(out, "            }}").unwrap();
+
+    writeln!(out, "        }}\n    }}\n").unwrap();
+}
+
+fn write_doc(out: &mut String, indent: usize, doc: Option<&str>) {
+    if let Some(doc) = doc {
+        writeln!(out, "{:indent$}#[doc = {:?}]", "", doc, indent = indent).unwrap();
+    }
+}
+
+/// The entries of a mapping, in the order of the file.
+fn entries(yaml: &Yaml) -> Vec<(String, &Yaml)> {
+    match yaml.as_hash() {
+        Some(hash) => hash.iter()
+            .filter_map(|(key, value)| key.as_str().map(|key| (key.to_owned(), value)))
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+fn integer(yaml: &Yaml) -> Option<u64> {
+    yaml.as_i64().map(|value| value as u64)
+}
+
+/// Documentation of an entry, preferring the short `brief` over the full `doc`, with its unit.
+fn doc(yaml: &Yaml) -> Option<String> {
+    let text = yaml["brief"].as_str().or_else(|| yaml["doc"].as_str())?.trim().to_owned();
+    match yaml["unit"].as_str() {
+        Some(unit) => Some(format!("{}\n\nUnit: `{}`", text, unit)),
+        None => Some(text),
+    }
+}
+
+/// The Rust name of an interface: `ODrive.Axis` becomes `Axis`, and `ODrive` itself `Board`.
+fn object_name(interface: &str) -> String {
+    match interface.rsplit('.').next().unwrap() {
+        "ODrive" => "Board".to_owned(),
+        name => name.to_owned(),
+    }
+}
+
+/// The Rust name of a value type. Generic names such as `ODrive.Axis.Error` are qualified with
+/// their owner, giving `AxisError`.
+fn value_type_name(full_name: &str) -> String {
+    let mut parts = full_name.rsplit('.');
+    let name = parts.next().unwrap();
+    match (name, parts.next()) {
+        ("Error", Some(owner)) | ("Mode", Some(owner)) => format!("{}{}", object_name(owner), name),
+        _ => name.to_owned(),
+    }
+}
+
+/// Returns true if `name` can be used as a Rust identifier.
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Converts `SCREAMING_CASE` or `snake_case` to `CamelCase`.
+fn camel_case(name: &str) -> String {
+    name.split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let word = word.to_ascii_lowercase();
+            let mut chars = word.chars();
+            chars.next().map(|first| first.to_ascii_uppercase().to_string() + chars.as_str()).unwrap_or_default()
+        })
+        .collect()
+}
+
+/// The method name of a member, with its firmware name given explicitly if they differ.
+fn member_ident(name: &str) -> String {
+    const KEYWORDS: &[&str] = &["type", "mod", "move", "ref", "loop", "match", "fn", "use", "self", "impl"];
+
+    let ident = name.to_ascii_lowercase();
+    if KEYWORDS.contains(&ident.as_str()) {
+        format!("{}_ = {:?}", ident, name)
+    } else if ident != name {
+        format!("{} = {:?}", ident, name)
+    } else {
+        ident
+    }
+}
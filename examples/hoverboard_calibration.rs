@@ -1,5 +1,4 @@
 use std::env::args;
-use std::io::Write;
 use std::path::Path;
 
 use serialport::SerialPortSettings;
@@ -12,10 +11,11 @@ fn main() {
     let args: Vec<String> = args().collect();
 
     // Create serial port settings
-    let mut settings = SerialPortSettings::default();
-
     // ODrive uses 115200 baud
-    settings.baud_rate = 115_200;
+    let settings = SerialPortSettings {
+        baud_rate: 115_200,
+        ..Default::default()
+    };
 
     // Create serial port
     let serial = serialport::posix::TTYPort::open(Path::new(&args[1]), &settings).expect("Failed to open port");
@@ -23,15 +23,15 @@ fn main() {
     // Create odrive connection
     let mut odrive = ODrive::new(serial);
 
-    odrive.run_state(AxisID::Zero, AxisState::MotorCalibration, true).unwrap();
-    odrive.run_state(AxisID::One, AxisState::MotorCalibration, true).unwrap();
+    odrive.change_state(AxisID::Zero, AxisState::MotorCalibration).unwrap();
+    odrive.change_state(AxisID::One, AxisState::MotorCalibration).unwrap();
 
     // set motor pre calibrated
     odrive.set_motor_pre_calibrated(AxisID::Zero, true).unwrap();
     odrive.set_motor_pre_calibrated(AxisID::One, true).unwrap();
 
-    odrive.run_state(AxisID::Zero, AxisState::EncoderOffsetCalibration, true).unwrap();
-    odrive.run_state(AxisID::One, AxisState::EncoderOffsetCalibration, true).unwrap();
+    odrive.change_state(AxisID::Zero, AxisState::EncoderOffsetCalibration).unwrap();
+    odrive.change_state(AxisID::One, AxisState::EncoderOffsetCalibration).unwrap();
 
     odrive.set_encoder_pre_calibrated(AxisID::Zero, true).unwrap();
     odrive.set_encoder_pre_calibrated(AxisID::One, true).unwrap();
@@ -1,5 +1,4 @@
 use std::env::args;
-use std::io::Write;
 use std::path::Path;
 use std::thread::sleep;
 use std::time::Duration;
@@ -14,10 +13,11 @@ fn main() {
     let args: Vec<String> = args().collect();
 
     // Create serial port settings
-    let mut settings = SerialPortSettings::default();
-
     // ODrive uses 115200 baud
-    settings.baud_rate = 115_200;
+    let settings = SerialPortSettings {
+        baud_rate: 115_200,
+        ..Default::default()
+    };
 
     // Create serial port
     let serial = serialport::posix::TTYPort::open(Path::new(&args[1]), &settings).expect("Failed to open port");
@@ -25,8 +25,8 @@ fn main() {
     // Create odrive connection
     let mut odrive = ODrive::new(serial);
 
-    odrive.run_state(AxisID::Zero, AxisState::ClosedLoopControl, false).unwrap();
-    odrive.run_state(AxisID::One, AxisState::ClosedLoopControl, false).unwrap();
+    odrive.request_state(AxisID::Zero, AxisState::ClosedLoopControl).unwrap();
+    odrive.request_state(AxisID::One, AxisState::ClosedLoopControl).unwrap();
 
     odrive.set_control_mode(AxisID::Zero, ControlMode::VelocityControl).unwrap();
     odrive.set_control_mode(AxisID::One, ControlMode::VelocityControl).unwrap();
@@ -13,10 +13,11 @@ fn main() {
     let args: Vec<String> = args().collect();
 
     // Create serial port settings
-    let mut settings = SerialPortSettings::default();
-
     // ODrive uses 115200 baud
-    settings.baud_rate = 115_200;
+    let settings = SerialPortSettings {
+        baud_rate: 115_200,
+        ..Default::default()
+    };
 
     // Create serial port
     let serial = serialport::posix::TTYPort::open(Path::new(&args[1]), &settings).expect("Failed to open port");
@@ -27,36 +28,41 @@ fn main() {
     sleep(Duration::from_millis(200));
     odrive.erase_configuration().unwrap();
 
-    odrive.set_motor_pole_pairs(AxisID::Zero, 15).unwrap();
-    odrive.set_motor_pole_pairs(AxisID::One, 15).unwrap();
-    odrive.set_motor_resistance_calib_max_voltage(AxisID::Zero, 4.0).unwrap();
-    odrive.set_motor_resistance_calib_max_voltage(AxisID::One, 4.0).unwrap();
-    odrive.set_motor_requested_current_range(AxisID::Zero, 25.0).unwrap();
-    odrive.set_motor_requested_current_range(AxisID::One, 25.0).unwrap();
-    odrive.set_motor_current_control_bandwidth(AxisID::Zero, 100.0).unwrap();
-    odrive.set_motor_current_control_bandwidth(AxisID::One, 100.0).unwrap();
-
-    odrive.set_encoder_mode(AxisID::Zero, EncoderMode::EncoderModeHall).unwrap();
-    odrive.set_encoder_mode(AxisID::One, EncoderMode::EncoderModeHall).unwrap();
-    odrive.set_encoder_cpr(AxisID::Zero, 90).unwrap();
-    odrive.set_encoder_cpr(AxisID::One, 90).unwrap();
-    odrive.set_encoder_bandwidth(AxisID::Zero, 100.0).unwrap();
-    odrive.set_encoder_bandwidth(AxisID::One, 100.0).unwrap();
-
-    odrive.set_position_gain(AxisID::Zero, 1.0).unwrap();
-    odrive.set_position_gain(AxisID::One, 1.0).unwrap();
-
-    odrive.set_velocity_gain(AxisID::Zero, 0.02).unwrap();
-    odrive.set_velocity_gain(AxisID::One, 0.02).unwrap();
-
-    odrive.set_velocity_integrator_gain(AxisID::Zero, 0.1).unwrap();
-    odrive.set_velocity_integrator_gain(AxisID::One, 0.1).unwrap();
-
-    odrive.set_velocity_limit(AxisID::Zero, 1000.0).unwrap();
-    odrive.set_velocity_limit(AxisID::One, 1000.0).unwrap();
-
-    odrive.set_control_mode(AxisID::Zero, ControlMode::VelocityControl).unwrap();
-    odrive.set_control_mode(AxisID::One, ControlMode::VelocityControl).unwrap();
+    // Restore the previous configuration if any step fails.
+    odrive.transaction(|odrive| {
+        odrive.set_motor_pole_pairs(AxisID::Zero, 15)?;
+        odrive.set_motor_pole_pairs(AxisID::One, 15)?;
+        odrive.set_motor_resistance_calib_max_voltage(AxisID::Zero, 4.0)?;
+        odrive.set_motor_resistance_calib_max_voltage(AxisID::One, 4.0)?;
+        odrive.set_motor_requested_current_range(AxisID::Zero, 25.0)?;
+        odrive.set_motor_requested_current_range(AxisID::One, 25.0)?;
+        odrive.set_motor_current_control_bandwidth(AxisID::Zero, 100.0)?;
+        odrive.set_motor_current_control_bandwidth(AxisID::One, 100.0)?;
+
+        odrive.set_encoder_mode(AxisID::Zero, EncoderMode::EncoderModeHall)?;
+        odrive.set_encoder_mode(AxisID::One, EncoderMode::EncoderModeHall)?;
+        odrive.set_encoder_cpr(AxisID::Zero, 90)?;
+        odrive.set_encoder_cpr(AxisID::One, 90)?;
+        odrive.set_encoder_bandwidth(AxisID::Zero, 100.0)?;
+        odrive.set_encoder_bandwidth(AxisID::One, 100.0)?;
+
+        odrive.set_position_gain(AxisID::Zero, 1.0)?;
+        odrive.set_position_gain(AxisID::One, 1.0)?;
+
+        odrive.set_velocity_gain(AxisID::Zero, 0.02)?;
+        odrive.set_velocity_gain(AxisID::One, 0.02)?;
+
+        odrive.set_velocity_integrator_gain(AxisID::Zero, 0.1)?;
+        odrive.set_velocity_integrator_gain(AxisID::One, 0.1)?;
+
+        odrive.set_velocity_limit(AxisID::Zero, 1000.0)?;
+        odrive.set_velocity_limit(AxisID::One, 1000.0)?;
+
+        odrive.set_control_mode(AxisID::Zero, ControlMode::VelocityControl)?;
+        odrive.set_control_mode(AxisID::One, ControlMode::VelocityControl)?;
+
+        Ok(())
+    }).unwrap();
 
     odrive.save_configuration().unwrap();
 }
\ No newline at end of file
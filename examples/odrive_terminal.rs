@@ -12,10 +12,11 @@ fn main() {
     let args: Vec<String> = args().collect();
 
     // Create serial port settings
-    let mut settings = SerialPortSettings::default();
-
     // ODrive uses 115200 baud
-    settings.baud_rate = 115_200;
+    let settings = SerialPortSettings {
+        baud_rate: 115_200,
+        ..Default::default()
+    };
 
     // Create serial port
     let serial = serialport::posix::TTYPort::open(Path::new(&args[1]), &settings).expect("Failed to open port");
@@ -15,10 +15,11 @@ fn main() {
     let args: Vec<String> = args().collect();
 
     // Create serial port settings
-    let mut settings = SerialPortSettings::default();
-
     // ODrive uses 115200 baud
-    settings.baud_rate = 115_200;
+    let settings = SerialPortSettings {
+        baud_rate: 115_200,
+        ..Default::default()
+    };
 
     // Create serial port
     let serial = serialport::posix::TTYPort::open(Path::new(&args[1]), &settings).expect("Failed to open port");
@@ -55,33 +56,33 @@ fn main() {
         if trimmed == "!exit" {
             break
         } else {
-            if let Some(first) = trimmed.chars().nth(0) {
+            if let Some(first) = trimmed.chars().next() {
                 match first {
                     // Run calibration sequence
                     'c' => {
                         println!("Requesting state {:?}", AxisState::MotorCalibration);
-                        odrive.run_state(AxisID::Zero, AxisState::MotorCalibration, true).unwrap();
-                        odrive.run_state(AxisID::One, AxisState::MotorCalibration, true).unwrap();
+                        odrive.change_state(AxisID::Zero, AxisState::MotorCalibration).unwrap();
+                        odrive.change_state(AxisID::One, AxisState::MotorCalibration).unwrap();
 
                         println!("Requesting state {:?}", AxisState::EncoderOffsetCalibration);
-                        odrive.run_state(AxisID::Zero, AxisState::EncoderOffsetCalibration, true).unwrap();
-                        odrive.run_state(AxisID::One, AxisState::EncoderOffsetCalibration, true).unwrap();
+                        odrive.change_state(AxisID::Zero, AxisState::EncoderOffsetCalibration).unwrap();
+                        odrive.change_state(AxisID::One, AxisState::EncoderOffsetCalibration).unwrap();
 
                         println!("Requesting state {:?}", AxisState::ClosedLoopControl);
-                        odrive.run_state(AxisID::Zero, AxisState::ClosedLoopControl, false).unwrap();
-                        odrive.run_state(AxisID::One, AxisState::ClosedLoopControl, false).unwrap();
+                        odrive.request_state(AxisID::Zero, AxisState::ClosedLoopControl).unwrap();
+                        odrive.request_state(AxisID::One, AxisState::ClosedLoopControl).unwrap();
                     }
                     '0' | '1' => {
                         let motor_num = if first == '0' { AxisID::Zero } else { AxisID::One };
 
                         println!("Axis {}: Requesting state {:?}", first, AxisState::MotorCalibration);
-                        odrive.run_state(motor_num, AxisState::MotorCalibration, true).unwrap();
+                        odrive.change_state(motor_num, AxisState::MotorCalibration).unwrap();
 
                         println!("Axis {}: Requesting state {:?}", first, AxisState::EncoderOffsetCalibration);
-                        odrive.run_state(motor_num, AxisState::EncoderOffsetCalibration, true).unwrap();
+                        odrive.change_state(motor_num, AxisState::EncoderOffsetCalibration).unwrap();
 
                         println!("Axis {}: Requesting state {:?}", first, AxisState::ClosedLoopControl);
-                        odrive.run_state(motor_num, AxisState::ClosedLoopControl, false).unwrap();
+                        odrive.request_state(motor_num, AxisState::ClosedLoopControl).unwrap();
                     }
                     // Sinusoidal test move
                     's' => {
@@ -108,7 +109,7 @@ fn main() {
                     // Read bus voltage
                     'b' => {
                         writeln!(odrive, "r vbus_voltage").unwrap();
-                        println!("Vbus voltage: {}", odrive.read_float().unwrap().unwrap());
+                        println!("Vbus voltage: {}", odrive.read_float().unwrap());
                     }
                     // print motor positions in a 10s loop
                     'p' => {
@@ -116,7 +117,7 @@ fn main() {
                         while start.elapsed().as_millis() < 10_000 {
                             for axis in 0..2 {
                                 writeln!(odrive, "r axis{}.encoder.pos_estimate", axis).unwrap();
-                                print!("{}\t", odrive.read_float().unwrap_or_default().unwrap_or_default());
+                                print!("{}\t", odrive.read_float().unwrap_or_default());
                             }
                             println!();
                         }
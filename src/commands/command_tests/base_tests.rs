@@ -1,8 +1,11 @@
+#![allow(clippy::bool_assert_comparison)]
+
 use super::*;
+use crate::enumerations::errors::AxisError;
 
 #[test]
 fn test_set_current() {
-    let mut odrive = init_odrive();
+    let mut odrive = init_odrive_with_firmware(FirmwareVersion::new(0, 4, 12), b"");
     odrive.set_current(AxisID::Zero, 24.0).unwrap();
     assert_eq!(b"c 0 24\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
     assert!(odrive.io_stream.get_mut().flushed)
@@ -62,7 +65,7 @@ fn test_read_int() {
     let mut odrive = init_odrive();
     odrive.io_stream.get_mut().read_buffer.append(&mut b"25\n".to_vec());
     odrive.io_stream.get_mut().read_buffer.reverse();
-    let result = odrive.read_int().unwrap().unwrap();
+    let result = odrive.read_int().unwrap();
     assert_eq!(25, result);
 }
 
@@ -71,9 +74,9 @@ fn test_multiple_read_int() {
     let mut odrive = init_odrive();
     odrive.io_stream.get_mut().read_buffer.append(&mut b"25\n78\n".to_vec());
     odrive.io_stream.get_mut().read_buffer.reverse();
-    let result = odrive.read_int().unwrap().unwrap();
+    let result = odrive.read_int().unwrap();
     assert_eq!(25, result);
-    let result = odrive.read_int().unwrap().unwrap();
+    let result = odrive.read_int().unwrap();
     assert_eq!(78, result);
 }
 
@@ -82,22 +85,44 @@ fn test_read_float() {
     let mut odrive = init_odrive();
     odrive.io_stream.get_mut().read_buffer.append(&mut b"25\n".to_vec());
     odrive.io_stream.get_mut().read_buffer.reverse();
-    let result = odrive.read_float().unwrap().unwrap();
+    let result = odrive.read_float().unwrap();
     assert_eq!(25.0, result);
 }
 
 #[test]
 fn test_get_velocity() {
-    let mut odrive = init_odrive();
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"");
     odrive.io_stream.get_mut().read_buffer.append(&mut b"25\n".to_vec());
     odrive.io_stream.get_mut().read_buffer.reverse();
-    let result = odrive.get_velocity(AxisID::Zero).unwrap().unwrap();
+    let result = odrive.get_velocity(AxisID::Zero).unwrap();
     assert_eq!(25.0, result);
-    assert_eq!(b"r axis0 .encoder.vel_estimate\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+    assert_eq!(b"r axis0.encoder.vel_estimate\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
     assert!(odrive.io_stream.get_mut().flushed)
 }
 
 #[test]
+fn test_get_feedback() {
+    let mut odrive = init_odrive_with_responses(b"1.5 -0.25\n");
+    let feedback = odrive.get_feedback(AxisID::One).unwrap();
+    assert_eq!(1.5, feedback.pos);
+    assert_eq!(-0.25, feedback.vel);
+    assert!(feedback.sent_at <= feedback.received_at);
+    assert_eq!(feedback.received_at - feedback.sent_at, feedback.latency());
+    assert_eq!(b"f 1\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+    assert!(odrive.io_stream.get_mut().flushed)
+}
+
+#[test]
+fn test_get_feedback_invalid() {
+    let mut odrive = init_odrive_with_responses(b"1.5\n");
+    match odrive.get_feedback(AxisID::Zero) {
+        Err(ODriveError::InvalidMessageReceived(message, _)) => assert_eq!("1.5", message),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+#[allow(deprecated)]
 fn test_run_state_instant_switch() {
     let mut odrive = init_odrive();
     odrive.io_stream.get_mut().read_buffer.append(&mut b"1\n".to_vec());
@@ -109,6 +134,7 @@ fn test_run_state_instant_switch() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_run_state_delayed_switch() {
     let mut odrive = init_odrive();
     odrive.io_stream.get_mut().read_buffer.append(&mut b"4\n1\n".to_vec());
@@ -118,3 +144,164 @@ fn test_run_state_delayed_switch() {
     assert_eq!(b"w axis0.requested_state 4\nr axis0.current_state\nr axis0.current_state\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
     assert!(odrive.io_stream.get_mut().flushed)
 }
+
+#[test]
+fn test_request_state() {
+    let mut odrive = init_odrive();
+    odrive.request_state(AxisID::One, AxisState::ClosedLoopControl).unwrap();
+    assert_eq!(b"w axis1.requested_state 8\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+    assert!(odrive.io_stream.get_mut().flushed)
+}
+
+#[test]
+fn test_change_state_terminal_reached() {
+    let mut odrive = init_odrive_with_responses(b"8\n");
+    let result = odrive.change_state(AxisID::Zero, AxisState::ClosedLoopControl).unwrap();
+    assert_eq!(StateOutcome::Reached, result);
+    assert_eq!(b"w axis0.requested_state 8\nr axis0.current_state\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_change_state_terminal_pending() {
+    let mut odrive = init_odrive_with_responses(b"1\n8\n8\n");
+    let result = odrive.change_state(AxisID::Zero, AxisState::ClosedLoopControl).unwrap();
+    assert_eq!(StateOutcome::Reached, result);
+    assert_eq!(b"w axis0.requested_state 8\nr axis0.current_state\nr axis0.requested_state\nr axis0.current_state\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_change_state_terminal_rejected() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"1\n0\n1\n");
+    let result = odrive.change_state(AxisID::Zero, AxisState::ClosedLoopControl).unwrap();
+    assert_eq!(StateOutcome::ReturnedToIdle(AxisErrors::from(AxisError::ErrorInvalidState)), result);
+    assert_eq!(b"w axis0.requested_state 8\nr axis0.current_state\nr axis0.requested_state\nr axis0.error\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_change_state_calibration_completed() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"4\n1\n0\n0\n");
+    let result = odrive.change_state(AxisID::Zero, AxisState::MotorCalibration).unwrap();
+    assert_eq!(StateOutcome::Reached, result);
+    assert_eq!(b"w axis0.requested_state 4\nr axis0.current_state\nr axis0.current_state\nr axis0.requested_state\nr axis0.error\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_change_state_calibration_failed() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"4\n1\n0\n64\n");
+    let result = odrive.change_state(AxisID::Zero, AxisState::MotorCalibration).unwrap();
+    assert_eq!(StateOutcome::ReturnedToIdle(AxisErrors::from(AxisError::ErrorMotorFailed)), result);
+}
+
+#[test]
+fn test_calibrate_motor() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_6, b"1\n0\n0\n");
+    assert_eq!(ProcedureResult::Success, odrive.calibrate_motor(AxisID::Zero).unwrap());
+    assert_eq!(b"w axis0.requested_state 4\nr axis0.current_state\nr axis0.requested_state\nr axis0.procedure_result\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_calibrate_encoder_offset_failed() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_6, b"1\n0\n5\n");
+    let result = odrive.calibrate_encoder_offset(AxisID::Zero).unwrap();
+    assert_eq!(ProcedureResult::PolePairCprMismatch, result);
+    assert!(!result.is_success());
+}
+
+#[test]
+fn test_run_full_calibration_legacy_firmware() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"1\n0\n0\n");
+    assert_eq!(ProcedureResult::Success, odrive.run_full_calibration(AxisID::One).unwrap());
+
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"1\n0\n320\n");
+    match odrive.run_full_calibration(AxisID::One) {
+        Err(ODriveError::AxisFailed(axis, errors)) => {
+            assert_eq!(AxisID::One, axis);
+            assert_eq!(vec![AxisError::ErrorMotorFailed, AxisError::ErrorEncoderFailed], errors.iter().collect::<Vec<_>>());
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_calibration_timeout_is_an_error() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_6, b"4\n");
+    odrive.set_timeouts(Timeouts { state_change: Duration::from_millis(0), ..Timeouts::default() });
+    match odrive.calibrate_motor(AxisID::Zero) {
+        Err(ODriveError::StateTimedOut(axis, state)) => {
+            assert_eq!(AxisID::Zero, axis);
+            assert_eq!(AxisState::MotorCalibration, state);
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_default_timeouts() {
+    let odrive = init_odrive();
+    assert_eq!(Duration::from_millis(1_000), odrive.timeouts().read);
+    assert_eq!(Duration::from_millis(10_000), odrive.timeouts().state_change);
+    assert_eq!(Duration::from_millis(10_000), odrive.timeouts().reconnect);
+}
+
+#[test]
+fn test_read_timeout() {
+    let mut odrive = ODrive::with_timeouts(MockStream::new(), Timeouts {
+        read: Duration::from_millis(10),
+        ..Timeouts::default()
+    });
+    let timer = Instant::now();
+    assert!(odrive.read_string().unwrap().is_none());
+    assert!(timer.elapsed() < Duration::from_millis(1_000));
+}
+
+#[test]
+fn test_change_state_with_timeout() {
+    let mut odrive = init_odrive_with_responses(b"4\n");
+    let result = odrive.change_state_with_timeout(AxisID::Zero, AxisState::MotorCalibration, Duration::from_millis(0)).unwrap();
+    assert_eq!(StateOutcome::TimedOut, result);
+    assert_eq!(b"w axis0.requested_state 4\nr axis0.current_state\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_read_partial_line_times_out() {
+    let mut odrive = init_odrive_with_responses(b"25");
+    odrive.set_timeouts(Timeouts { read: Duration::from_millis(10), ..Timeouts::default() });
+    assert!(odrive.read_string().unwrap().is_none());
+}
+
+#[test]
+fn test_read_trickled_line_times_out() {
+    let mut odrive = init_odrive_with_responses(&[b'2'; 20_000]);
+    odrive.set_timeouts(Timeouts { read: Duration::from_millis(0), ..Timeouts::default() });
+    odrive.set_sync_options(SyncOptions { max_line_length: 16_384, ..SyncOptions::default() });
+    match odrive.read_odrive_response() {
+        Err(ODriveError::NoMessageReceived(_)) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_read_stream_timeout_is_not_an_error() {
+    let mut odrive = init_odrive();
+    odrive.set_timeouts(Timeouts { read: Duration::from_millis(10), ..Timeouts::default() });
+    odrive.io_stream.get_mut().read_error = Some(ErrorKind::TimedOut);
+    match odrive.read_odrive_response() {
+        Err(ODriveError::NoMessageReceived(_)) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_read_io_error_propagated() {
+    let mut odrive = init_odrive();
+    odrive.io_stream.get_mut().read_error = Some(ErrorKind::BrokenPipe);
+    match odrive.read_odrive_response() {
+        Err(ODriveError::Io(error)) => assert_eq!(ErrorKind::BrokenPipe, error.kind()),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(ErrorKind::BrokenPipe, odrive.read_string().unwrap_err().kind());
+}
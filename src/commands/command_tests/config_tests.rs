@@ -0,0 +1,230 @@
+use super::*;
+use crate::config::{ApplyOutcome, ConfigValue, ConfigValues, DeviceConfig};
+use crate::properties::{fw_0_4, ValueKind};
+
+fn reply(kind: ValueKind) -> &'static [u8] {
+    match kind {
+        ValueKind::Bool => b"1\n",
+        ValueKind::Integer => b"2\n",
+        ValueKind::Float => b"0.5\n",
+    }
+}
+
+/// Answers every configurable property of a 0.4 board, except `<axis>.encoder.config.offset_float`.
+fn snapshot_responses() -> Vec<u8> {
+    let mut responses = Vec::new();
+    for &(_, kind) in fw_0_4::BOARD_CONFIG {
+        responses.extend_from_slice(reply(kind));
+    }
+    for _ in 0..2 {
+        for &(path, kind) in fw_0_4::AXIS_CONFIG {
+            if path == "encoder.config.offset_float" {
+                responses.extend_from_slice(b"invalid property\n");
+            } else {
+                responses.extend_from_slice(reply(kind));
+            }
+        }
+    }
+    responses
+}
+
+#[test]
+fn test_read_config_snapshot() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_4, &snapshot_responses());
+    let config = odrive.read_config_snapshot().unwrap();
+
+    assert_eq!(FIRMWARE_0_4, config.firmware);
+    assert_eq!(fw_0_4::BOARD_CONFIG.len(), config.board.len());
+    assert_eq!(Some(&ConfigValue::Float(0.5)), config.board.get("config.dc_bus_overvoltage_trip_level"));
+    assert_eq!(2, config.axes.len());
+    for axis in &config.axes {
+        assert_eq!(fw_0_4::AXIS_CONFIG.len() - 1, axis.len());
+        assert_eq!(Some(&ConfigValue::Integer(2)), axis.get("motor.config.pole_pairs"));
+        assert_eq!(Some(&ConfigValue::Bool(true)), axis.get("encoder.config.use_index"));
+        assert_eq!(None, axis.get("encoder.config.offset_float"));
+    }
+
+    let written = String::from_utf8(odrive.io_stream.get_mut().write_buffer.clone()).unwrap();
+    assert!(written.starts_with("r config.brake_resistance\n"));
+    assert!(written.contains("r axis1.motor.config.pole_pairs\n"));
+}
+
+#[test]
+fn test_read_config_snapshot_invalid_value() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_4, b"abc\n");
+    match odrive.read_config_snapshot() {
+        Err(ODriveError::InvalidMessageReceived(message, _)) => assert_eq!("abc", message),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_config_value_format() {
+    assert_eq!("1", ConfigValue::Bool(true).to_string());
+    assert_eq!("-3", ConfigValue::Integer(-3).to_string());
+    assert_eq!("0.25", ConfigValue::Float(0.25).to_string());
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_config_snapshot_serde() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_4, &snapshot_responses());
+    let config = odrive.read_config_snapshot().unwrap();
+
+    let json = serde_json::to_string(&config).unwrap();
+    assert!(json.contains("\"motor.config.pole_pairs\":2"));
+    assert!(json.contains("\"encoder.config.use_index\":true"));
+    assert_eq!(config, serde_json::from_str::<crate::config::DeviceConfig>(&json).unwrap());
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_config_snapshot_serde_non_finite() {
+    let config = device_config(FIRMWARE_0_5, &[("config.dc_max_positive_current", ConfigValue::Float(f32::INFINITY)),
+                                              ("config.dc_max_negative_current", ConfigValue::Float(f32::NEG_INFINITY))],
+                               &[("controller.config.vel_integrator_limit", ConfigValue::Float(f32::INFINITY)),
+                                 ("controller.config.vel_gain", ConfigValue::Float(0.25))]);
+
+    let json = serde_json::to_string(&config).unwrap();
+    assert!(json.contains("\"config.dc_max_positive_current\":\"inf\""));
+    assert!(json.contains("\"config.dc_max_negative_current\":\"-inf\""));
+    assert_eq!(config, serde_json::from_str::<DeviceConfig>(&json).unwrap());
+
+    match serde_json::from_str::<ConfigValue>("\"nan\"").unwrap() {
+        ConfigValue::Float(value) => assert!(value.is_nan()),
+        other => panic!("unexpected value: {:?}", other),
+    }
+    assert!(serde_json::from_str::<ConfigValue>("\"fast\"").is_err());
+}
+
+fn device_config(firmware: FirmwareVersion, board: &[(&str, ConfigValue)], axis0: &[(&str, ConfigValue)]) -> DeviceConfig {
+    let values = |values: &[(&str, ConfigValue)]| -> ConfigValues {
+        values.iter().map(|(path, value)| ((*path).to_owned(), *value)).collect()
+    };
+    DeviceConfig { firmware, board: values(board), axes: vec![values(axis0)] }
+}
+
+#[test]
+fn test_config_value_approx_eq() {
+    assert!(ConfigValue::Float(0.1).approx_eq(&ConfigValue::Float(0.100_001)));
+    assert!(ConfigValue::Float(24_000.0).approx_eq(&ConfigValue::Float(24_000.1)));
+    assert!(!ConfigValue::Float(0.1).approx_eq(&ConfigValue::Float(0.11)));
+    assert!(ConfigValue::Integer(4).approx_eq(&ConfigValue::Float(4.0)));
+    assert!(!ConfigValue::Integer(4).approx_eq(&ConfigValue::Integer(5)));
+    assert!(!ConfigValue::Bool(true).approx_eq(&ConfigValue::Integer(1)));
+}
+
+#[test]
+fn test_config_value_approx_eq_non_finite() {
+    assert!(ConfigValue::Float(f32::INFINITY).approx_eq(&ConfigValue::Float(f32::INFINITY)));
+    assert!(ConfigValue::Float(f32::NAN).approx_eq(&ConfigValue::Float(f32::NAN)));
+    assert!(!ConfigValue::Float(f32::INFINITY).approx_eq(&ConfigValue::Float(f32::NEG_INFINITY)));
+    assert!(!ConfigValue::Float(f32::INFINITY).approx_eq(&ConfigValue::Float(1e30)));
+    assert!(!ConfigValue::Float(f32::NAN).approx_eq(&ConfigValue::Float(0.0)));
+}
+
+#[test]
+fn test_config_value_approx_eq_small_magnitude() {
+    // Phase inductances are in the order of tens of microhenries.
+    assert!(ConfigValue::Float(2e-5).approx_eq(&ConfigValue::Float(2.000_01e-5)));
+    assert!(!ConfigValue::Float(2e-5).approx_eq(&ConfigValue::Float(2.9e-5)));
+    assert!(ConfigValue::Float(0.0).approx_eq(&ConfigValue::Float(5e-7)));
+    assert!(!ConfigValue::Float(0.0).approx_eq(&ConfigValue::Float(2e-6)));
+}
+
+#[test]
+fn test_config_diff() {
+    let current = device_config(FIRMWARE_0_5, &[("config.brake_resistance", ConfigValue::Float(2.0))],
+                                &[("motor.config.pole_pairs", ConfigValue::Integer(7)),
+                                  ("encoder.config.use_index", ConfigValue::Bool(false))]);
+    let desired = device_config(FIRMWARE_0_5, &[("config.brake_resistance", ConfigValue::Float(2.000_001))],
+                                &[("motor.config.pole_pairs", ConfigValue::Integer(15)),
+                                  ("encoder.config.cpr", ConfigValue::Integer(90))]);
+
+    let differences = current.diff(&desired);
+    assert_eq!(2, differences.len());
+    assert_eq!("axis0.encoder.config.cpr", differences[0].path);
+    assert_eq!(None, differences[0].current);
+    assert_eq!("axis0.motor.config.pole_pairs", differences[1].path);
+    assert_eq!(Some(ConfigValue::Integer(7)), differences[1].current);
+    assert_eq!(ConfigValue::Integer(15), differences[1].desired);
+}
+
+#[test]
+fn test_apply_config() {
+    let mut desired = init_odrive_with_firmware(FIRMWARE_0_4, &snapshot_responses()).read_config_snapshot().unwrap();
+    desired.board.insert("config.dc_bus_overvoltage_trip_level".to_owned(), ConfigValue::Float(0.500_001));
+    desired.axes[0].insert("encoder.config.offset_float".to_owned(), ConfigValue::Float(1.0));
+    desired.axes[0].insert("motor.config.pole_pairs".to_owned(), ConfigValue::Integer(7));
+    desired.axes[1].insert("controller.config.vel_limit".to_owned(), ConfigValue::Float(10.0));
+
+    let mut responses = snapshot_responses();
+    responses.extend_from_slice(b"invalid property\n7\n9.5\n");
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_4, &responses);
+    let report = odrive.apply_config(&desired).unwrap();
+
+    assert_eq!(3, report.properties.len());
+    assert!(!report.is_success());
+    assert_eq!(2, report.failures().count());
+    match &report.properties[0].outcome {
+        ApplyOutcome::Failed(ODriveError::UnknownProperty(_)) => {}
+        other => panic!("unexpected outcome: {:?}", other),
+    }
+    assert!(matches!(report.properties[1].outcome, ApplyOutcome::Verified));
+    assert_eq!("axis1.controller.config.vel_limit", report.properties[2].difference.path);
+    match report.properties[2].outcome {
+        ApplyOutcome::Mismatch(value) => assert_eq!(ConfigValue::Float(9.5), value),
+        ref other => panic!("unexpected outcome: {:?}", other),
+    }
+
+    let written = String::from_utf8(odrive.io_stream.get_mut().write_buffer.clone()).unwrap();
+    assert!(written.ends_with("w axis0.encoder.config.offset_float 1\nr axis0.encoder.config.offset_float\n\
+w axis0.motor.config.pole_pairs 7\nr axis0.motor.config.pole_pairs\n\
+w axis1.controller.config.vel_limit 10\nr axis1.controller.config.vel_limit\n"));
+}
+
+#[test]
+fn test_apply_config_integer_for_float_property() {
+    let mut desired = init_odrive_with_firmware(FIRMWARE_0_4, &snapshot_responses()).read_config_snapshot().unwrap();
+    desired.axes[0].insert("controller.config.vel_limit".to_owned(), ConfigValue::Integer(10));
+
+    let mut responses = snapshot_responses();
+    responses.extend_from_slice(b"10.0\n");
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_4, &responses);
+    let report = odrive.apply_config(&desired).unwrap();
+
+    assert_eq!(1, report.properties.len());
+    assert!(report.is_success(), "{:?}", report);
+}
+
+#[test]
+fn test_apply_config_read_back_timeout() {
+    let mut desired = init_odrive_with_firmware(FIRMWARE_0_4, &snapshot_responses()).read_config_snapshot().unwrap();
+    desired.axes[0].insert("motor.config.pole_pairs".to_owned(), ConfigValue::Integer(7));
+    desired.axes[1].insert("controller.config.vel_limit".to_owned(), ConfigValue::Float(10.0));
+
+    // The read-back of the last property is never answered.
+    let mut responses = snapshot_responses();
+    responses.extend_from_slice(b"7\n");
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_4, &responses);
+    odrive.set_timeouts(Timeouts { read: Duration::from_millis(10), ..Timeouts::default() });
+    let report = odrive.apply_config(&desired).unwrap();
+
+    assert_eq!(2, report.properties.len());
+    assert!(matches!(report.properties[0].outcome, ApplyOutcome::Verified));
+    match &report.properties[1].outcome {
+        ApplyOutcome::Failed(ODriveError::NoMessageReceived(context)) => {
+            assert_eq!("r axis1.controller.config.vel_limit", context.request);
+        }
+        other => panic!("unexpected outcome: {:?}", other),
+    }
+}
+
+#[test]
+fn test_apply_config_other_firmware() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_4, &snapshot_responses());
+    match odrive.apply_config(&device_config(FIRMWARE_0_5, &[], &[])) {
+        Err(ODriveError::InvalidConfiguration(_)) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
@@ -15,3 +15,132 @@ fn test_erase_configuration() {
     assert_eq!(b"se\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
     assert!(odrive.io_stream.get_mut().flushed)
 }
+
+#[test]
+fn test_reboot() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"");
+    odrive.reboot().unwrap();
+    assert_eq!(b"sr\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+    assert!(odrive.io_stream.get_mut().flushed);
+    assert_eq!(None, odrive.firmware);
+}
+
+fn rebooted_stream(replies: &[&[u8]]) -> MockStream {
+    let mut stream = MockStream::new();
+    stream.replies = replies.iter().map(|reply| reply.to_vec()).collect();
+    stream
+}
+
+#[test]
+fn test_reboot_and_reconnect() {
+    let timeouts = Timeouts { read: Duration::from_millis(10), ..Timeouts::default() };
+    let mut odrive = ODrive::with_timeouts(MockStream::new(), timeouts);
+    odrive.firmware = Some(FIRMWARE_0_5);
+    let sync = SyncOptions { sentinel: false, ..SyncOptions::default() };
+    odrive.set_sync_options(sync);
+
+    // The device has not enumerated yet, then does not answer, then is ready.
+    let mut attempts = vec![
+        Ok(rebooted_stream(&[b"0\n", b"6\n", b"10\n"])),
+        Ok(rebooted_stream(&[])),
+        Err(io::Error::from(ErrorKind::NotFound)),
+    ];
+    let mut odrive = odrive.reboot_and_reconnect(|| attempts.pop().unwrap()).unwrap();
+
+    assert!(attempts.is_empty());
+    assert_eq!(FIRMWARE_0_6, odrive.firmware_version().unwrap());
+    assert_eq!(timeouts, odrive.timeouts());
+    assert_eq!(sync, odrive.sync_options());
+    assert_eq!(b"r fw_version_major\nr fw_version_minor\nr fw_version_revision\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_reconnect_timeout() {
+    let odrive = ODrive::with_timeouts(MockStream::new(), Timeouts {
+        reconnect: Duration::from_millis(0),
+        ..Timeouts::default()
+    });
+    let mut attempts = 0;
+    let result = odrive.reconnect(|| {
+        attempts += 1;
+        Err(io::Error::from(ErrorKind::NotFound))
+    });
+    match result {
+        Err(ODriveError::Io(error)) => assert_eq!(ErrorKind::NotFound, error.kind()),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(1, attempts);
+}
+
+fn verify_current_limit() -> SaveOptions {
+    SaveOptions { verify: vec!["axis0.motor.config.current_lim".to_owned()], ..SaveOptions::default() }
+}
+
+#[test]
+fn test_save_configuration_checked() {
+    let odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"1\n1\n10\n");
+    let mut attempts = vec![Ok(rebooted_stream(&[b"0\n", b"5\n", b"6\n", b"10\n"]))];
+    let mut odrive = odrive.save_configuration_checked(&verify_current_limit(), || attempts.pop().unwrap()).unwrap();
+    assert_eq!(FIRMWARE_0_5, odrive.firmware_version().unwrap());
+    assert_eq!(b"r fw_version_major\nr fw_version_minor\nr fw_version_revision\nr axis0.motor.config.current_lim\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_save_configuration_checked_not_persisted() {
+    let odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"1\n1\n10\n");
+    let mut attempts = vec![Ok(rebooted_stream(&[b"0\n", b"5\n", b"6\n", b"20\n"]))];
+    let error = odrive.save_configuration_checked(&verify_current_limit(), || attempts.pop().unwrap()).unwrap_err();
+    match error.error {
+        ODriveError::NotPersisted(paths) => assert_eq!(vec!["axis0.motor.config.current_lim".to_owned()], paths),
+        other => panic!("unexpected error: {:?}", other),
+    }
+    // The reopened connection is kept.
+    let mut odrive = error.odrive.unwrap();
+    assert_eq!(b"r fw_version_major\nr fw_version_minor\nr fw_version_revision\nr axis0.motor.config.current_lim\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_save_configuration_checked_not_idle() {
+    let odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"1\n8\n");
+    let error = odrive.save_configuration_checked(&SaveOptions::default(), || panic!("should not reconnect")).unwrap_err();
+    match error.error {
+        ODriveError::InvalidConfiguration(_) => {}
+        other => panic!("unexpected error: {:?}", other),
+    }
+    // The original connection is kept, without anything saved.
+    let mut odrive = error.odrive.unwrap();
+    assert_eq!(b"r axis0.current_state\nr axis1.current_state\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_save_configuration_checked_idles_axes_0_4() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_4, b"8\n1\n1\n10\n");
+    let options = SaveOptions { idle_axes: true, ..verify_current_limit() };
+    assert_eq!(vec!["10".to_owned()], odrive.save_and_reboot(&options).unwrap());
+    // Firmware 0.4 does not reboot after saving by itself.
+    assert_eq!(b"r axis0.current_state\nw axis0.requested_state 1\nr axis0.current_state\nr axis1.current_state\n\
+r axis0.motor.config.current_lim\nss\nsr\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_save_configuration_checked_reconnects_0_4() {
+    let odrive = init_odrive_with_firmware(FIRMWARE_0_4, b"1\n1\n10\n");
+    let mut attempts = vec![Ok(rebooted_stream(&[b"0\n", b"4\n", b"12\n", b"10\n"]))];
+    let mut odrive = odrive.save_configuration_checked(&verify_current_limit(), || attempts.pop().unwrap()).unwrap();
+    assert!(attempts.is_empty());
+    assert_eq!(FIRMWARE_0_4, odrive.firmware_version().unwrap());
+    assert_eq!(b"r fw_version_major\nr fw_version_minor\nr fw_version_revision\nr axis0.motor.config.current_lim\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_save_configuration_checked_single_axis_0_6() {
+    let odrive = init_odrive_with_firmware(FIRMWARE_0_6, b"1\n");
+    let mut attempts = vec![Ok(rebooted_stream(&[b"0\n", b"6\n", b"10\n"]))];
+    let mut odrive = odrive.save_configuration_checked(&SaveOptions::default(), || attempts.pop().unwrap()).unwrap();
+    assert_eq!(FIRMWARE_0_6, odrive.firmware_version().unwrap());
+}
@@ -34,8 +34,52 @@ fn test_set_vel_limit() {
 
 #[test]
 fn test_set_control_mode() {
-    let mut odrive = init_odrive();
+    let mut odrive = init_odrive_with_firmware(FirmwareVersion::new(0, 4, 12), b"");
     odrive.set_control_mode(AxisID::Zero, ControlMode::VelocityControl).unwrap();
     assert_eq!(b"w axis0.controller.config.control_mode 2\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
     assert!(odrive.io_stream.get_mut().flushed)
 }
+
+#[test]
+fn test_read_pos_gain() {
+    let mut odrive = init_odrive_with_responses(b"20.0\n");
+    assert_eq!(20.0, odrive.read_position_gain(AxisID::Zero).unwrap());
+    assert_eq!(b"r axis0.controller.config.pos_gain\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_read_vel_gain() {
+    let mut odrive = init_odrive_with_responses(b"0.02\n");
+    assert_eq!(0.02, odrive.read_velocity_gain(AxisID::Zero).unwrap());
+    assert_eq!(b"r axis0.controller.config.vel_gain\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_read_vel_integrator_gain() {
+    let mut odrive = init_odrive_with_responses(b"0.1\n");
+    assert_eq!(0.1, odrive.read_velocity_integrator_gain(AxisID::Zero).unwrap());
+    assert_eq!(b"r axis0.controller.config.vel_integrator_gain\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_read_vel_limit() {
+    let mut odrive = init_odrive_with_responses(b"1000.0\n");
+    assert_eq!(1000.0, odrive.read_velocity_limit(AxisID::One).unwrap());
+    assert_eq!(b"r axis1.controller.config.vel_limit\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_read_control_mode() {
+    let mut odrive = init_odrive_with_firmware(FirmwareVersion::new(0, 4, 12), b"2\n");
+    assert_eq!(ControlMode::VelocityControl, odrive.read_control_mode(AxisID::Zero).unwrap());
+    assert_eq!(b"r axis0.controller.config.control_mode\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_read_control_mode_out_of_range() {
+    let mut odrive = init_odrive_with_firmware(FirmwareVersion::new(0, 4, 12), b"9\n");
+    match odrive.read_control_mode(AxisID::Zero) {
+        Err(ODriveError::InvalidMessageReceived(message, _)) => assert_eq!("9", message),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
@@ -2,7 +2,7 @@ use super::*;
 
 #[test]
 fn test_set_encoder_mode() {
-    let mut odrive = init_odrive();
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"");
     odrive.set_encoder_mode(AxisID::Zero, EncoderMode::EncoderModeHall).unwrap();
     assert_eq!(b"w axis0.encoder.config.mode 1\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
     assert!(odrive.io_stream.get_mut().flushed)
@@ -10,7 +10,7 @@ fn test_set_encoder_mode() {
 
 #[test]
 fn test_set_encoder_cpr() {
-    let mut odrive = init_odrive();
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"");
     odrive.set_encoder_cpr(AxisID::Zero, 50).unwrap();
     assert_eq!(b"w axis0.encoder.config.cpr 50\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
     assert!(odrive.io_stream.get_mut().flushed)
@@ -18,7 +18,7 @@ fn test_set_encoder_cpr() {
 
 #[test]
 fn test_set_encoder_bandwidth() {
-    let mut odrive = init_odrive();
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"");
     odrive.set_encoder_bandwidth(AxisID::Zero, 50.0).unwrap();
     assert_eq!(b"w axis0.encoder.config.bandwidth 50\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
     assert!(odrive.io_stream.get_mut().flushed)
@@ -26,8 +26,127 @@ fn test_set_encoder_bandwidth() {
 
 #[test]
 fn test_set_encoder_pre_calibration() {
-    let mut odrive = init_odrive();
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"");
     odrive.set_encoder_pre_calibrated(AxisID::Zero, true).unwrap();
     assert_eq!(b"w axis0.encoder.config.pre_calibrated 1\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
     assert!(odrive.io_stream.get_mut().flushed)
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_read_encoder_mode() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"1\n");
+    assert_eq!(EncoderMode::EncoderModeHall, odrive.read_encoder_mode(AxisID::Zero).unwrap());
+    assert_eq!(b"r axis0.encoder.config.mode\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_read_encoder_mode_out_of_range() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"7\n");
+    match odrive.read_encoder_mode(AxisID::Zero) {
+        Err(ODriveError::InvalidMessageReceived(message, _)) => assert_eq!("7", message),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_read_encoder_cpr() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"90\n");
+    assert_eq!(90, odrive.read_encoder_cpr(AxisID::One).unwrap());
+    assert_eq!(b"r axis1.encoder.config.cpr\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_read_encoder_bandwidth() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"100.0\n");
+    assert_eq!(100.0, odrive.read_encoder_bandwidth(AxisID::Zero).unwrap());
+    assert_eq!(b"r axis0.encoder.config.bandwidth\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_read_encoder_pre_calibrated() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"0\n");
+    assert!(!odrive.read_encoder_pre_calibrated(AxisID::Zero).unwrap());
+    assert_eq!(b"r axis0.encoder.config.pre_calibrated\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_set_encoder_mode_spi() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"");
+    odrive.set_encoder_mode(AxisID::One, EncoderMode::EncoderModeSpiAbsAms).unwrap();
+    assert_eq!(b"w axis1.encoder.config.mode 257\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_set_encoder_mode_unsupported() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_4, b"");
+    match odrive.set_encoder_mode(AxisID::Zero, EncoderMode::EncoderModeSpiAbsRls) {
+        Err(ODriveError::Unsupported(_, firmware)) => assert_eq!(FIRMWARE_0_4, firmware),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(odrive.io_stream.get_mut().write_buffer.is_empty());
+}
+
+#[test]
+fn test_read_encoder_mode_spi() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"260\n2\n");
+    assert_eq!(EncoderMode::EncoderModeSpiAbsMa732, odrive.read_encoder_mode(AxisID::Zero).unwrap());
+    assert_eq!(EncoderMode::EncoderModeSincos, odrive.read_encoder_mode(AxisID::Zero).unwrap());
+}
+
+#[test]
+fn test_encoder_mode_try_from() {
+    use std::convert::TryFrom;
+    assert_eq!(Ok(EncoderMode::EncoderModeSpiAbsCui), EncoderMode::try_from(0x100));
+    assert_eq!(Err(0x105), EncoderMode::try_from(0x105));
+}
+
+#[test]
+fn test_set_encoder_linear_count() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"");
+    odrive.set_encoder_linear_count(AxisID::One, -5).unwrap();
+    assert_eq!(b"es 1 -5\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+    assert!(odrive.io_stream.get_mut().flushed)
+}
+
+#[test]
+fn test_set_encoder_linear_count_0_6() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_6, b"");
+    match odrive.set_encoder_linear_count(AxisID::Zero, 0) {
+        Err(ODriveError::Unsupported(_, firmware)) => assert_eq!(FIRMWARE_0_6, firmware),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(odrive.io_stream.get_mut().write_buffer.is_empty());
+}
+
+#[test]
+fn test_set_current_position_as_idle() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"1\n2.5\n8192\n");
+    assert_eq!(0.25, odrive.set_current_position_as(AxisID::Zero, 0.25).unwrap());
+    assert_eq!(b"r axis0.current_state\nr axis0.encoder.pos_estimate\nr axis0.encoder.config.cpr\nes 0 2048\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_set_current_position_as_rounds_to_counts() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"1\n2.5\n4\n");
+    assert_eq!(0.5, odrive.set_current_position_as(AxisID::Zero, 0.4).unwrap());
+    assert!(String::from_utf8(odrive.io_stream.get_mut().write_buffer.clone()).unwrap().ends_with("es 0 2\n"));
+}
+
+#[test]
+fn test_set_current_position_as_closed_loop_0_4() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_4, b"8\n1000\n1010\n");
+    assert_eq!(0.0, odrive.set_current_position_as(AxisID::One, 0.0).unwrap());
+    assert_eq!(b"r axis1.current_state\nr axis1.encoder.pos_estimate\nr axis1.controller.pos_setpoint\n\
+es 1 0\nw axis1.controller.pos_setpoint 10\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_set_current_position_as_closed_loop_0_6() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_6, b"8\n0.5\n0.75\n");
+    assert_eq!(2.0, odrive.set_current_position_as(AxisID::Zero, 2.0).unwrap());
+    assert_eq!(b"r axis0.current_state\nr axis0.pos_vel_mapper.pos_rel\nr axis0.controller.input_pos\n\
+es 0 2\nw axis0.controller.input_pos 2.25\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
@@ -0,0 +1,142 @@
+use super::*;
+use crate::enumerations::errors::{ActiveError, AxisError, ControllerError, EncoderError, MotorError};
+
+#[test]
+fn test_read_axis_error() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"576\n");
+    let result = odrive.read_axis_error(AxisID::Zero).unwrap();
+    assert!(result.contains(AxisError::ErrorMotorFailed));
+    assert!(result.contains(AxisError::ErrorControllerFailed));
+    assert!(!result.contains(AxisError::ErrorEncoderFailed));
+    assert_eq!(0x240, result.bits());
+    assert_eq!(b"r axis0.error\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_read_axis_error_iter() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"576\n");
+    let result = odrive.read_axis_error(AxisID::Zero).unwrap();
+    let flags: Vec<AxisError> = result.iter().collect();
+    assert_eq!(vec![AxisError::ErrorMotorFailed, AxisError::ErrorControllerFailed], flags);
+}
+
+#[test]
+fn test_read_motor_error_unknown_bits() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"2049\n");
+    let result = odrive.read_motor_error(AxisID::One).unwrap();
+    assert!(result.contains(MotorError::ErrorPhaseResistanceOutOfRange));
+    assert_eq!(0x800, result.unknown_bits());
+    assert_eq!(0x801, result.bits());
+    assert_eq!(b"r axis1.motor.error\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_read_encoder_error_none() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"0\n");
+    let result = odrive.read_encoder_error(AxisID::Zero).unwrap();
+    assert!(result.is_empty());
+    assert!(result.contains(EncoderError::ErrorNone));
+    assert_eq!(b"r axis0.encoder.error\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_read_controller_error_invalid() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"abc\n");
+    match odrive.read_controller_error(AxisID::Zero) {
+        Err(ODriveError::InvalidMessageReceived(message, context)) => {
+            assert_eq!("abc", message);
+            assert_eq!("r axis0.controller.error", context.request);
+            assert_eq!(Some(AxisID::Zero), context.axis);
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_read_all_errors() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"64\n1\n0\n1\n");
+    let result = odrive.read_all_errors(AxisID::Zero).unwrap();
+    assert!(!result.is_empty());
+    assert_eq!(AxisErrors::from(AxisError::ErrorMotorFailed), result.axis);
+    assert_eq!(MotorErrors::from(MotorError::ErrorPhaseResistanceOutOfRange), result.motor);
+    assert!(result.encoder.is_empty());
+    assert!(result.controller.contains(ControllerError::ErrorOverspeed));
+    assert_eq!(b"r axis0.error\nr axis0.motor.error\nr axis0.encoder.error\nr axis0.controller.error\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_error_set_debug() {
+    let errors = AxisErrors::from_bits(0x1040);
+    assert_eq!("{ErrorMotorFailed, 0x1000}", format!("{:?}", errors));
+}
+
+#[test]
+fn test_clear_errors_legacy_firmware() {
+    let mut odrive = init_odrive_with_responses(b"0\n4\n12\n64\n1\n0\n0\n");
+    let result = odrive.clear_errors(AxisID::One).unwrap();
+    assert!(result.axis.contains(AxisError::ErrorMotorFailed));
+    assert!(result.motor.contains(MotorError::ErrorPhaseResistanceOutOfRange));
+    assert_eq!(b"r fw_version_major\nr fw_version_minor\nr fw_version_revision\n\
+                 r axis1.error\nr axis1.motor.error\nr axis1.encoder.error\nr axis1.controller.error\n\
+                 w axis1.error 0\nw axis1.motor.error 0\nw axis1.encoder.error 0\nw axis1.controller.error 0\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+    assert!(odrive.io_stream.get_mut().flushed)
+}
+
+#[test]
+fn test_clear_errors_sc_command() {
+    let mut odrive = init_odrive_with_responses(b"0\n5\n2\n0\n0\n0\n1\n");
+    let result = odrive.clear_errors(AxisID::Zero).unwrap();
+    assert!(result.controller.contains(ControllerError::ErrorOverspeed));
+    assert!(odrive.io_stream.get_mut().write_buffer.starts_with(b"r fw_version_major\n"));
+    assert!(odrive.io_stream.get_mut().write_buffer.ends_with(b"controller.error\nsc\n"));
+}
+
+#[test]
+fn test_clear_all_errors_sc_command() {
+    let mut odrive = init_odrive_with_responses(b"0\n5\n4\n0\n0\n0\n0\n1\n0\n0\n0\n");
+    let result = odrive.clear_all_errors().unwrap();
+    assert!(result[0].is_empty());
+    assert!(result[1].axis.contains(AxisError::ErrorInvalidState));
+    assert!(odrive.io_stream.get_mut().write_buffer.starts_with(b"r fw_version_major\n"));
+    assert!(odrive.io_stream.get_mut().write_buffer.ends_with(b"controller.error\nsc\n"));
+}
+
+#[test]
+fn test_firmware_version_cached() {
+    let mut odrive = init_odrive_with_responses(b"0\n5\n1\n");
+    assert_eq!(FirmwareVersion::new(0, 5, 1), odrive.firmware_version().unwrap());
+    assert_eq!(FirmwareVersion::new(0, 5, 1), odrive.firmware_version().unwrap());
+    assert_eq!(b"r fw_version_major\nr fw_version_minor\nr fw_version_revision\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_read_active_errors() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_6, b"1073745920\n33554432\n");
+    let errors = odrive.read_active_errors(AxisID::Zero).unwrap();
+    assert_eq!(vec![ActiveError::CurrentLimitViolation, ActiveError::CalibrationError],
+               errors.iter().collect::<Vec<_>>());
+    let reason = odrive.read_disarm_reason(AxisID::Zero).unwrap();
+    assert!(reason.contains(ActiveError::EstopRequested));
+    assert_eq!(b"r axis0.active_errors\nr axis0.disarm_reason\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_read_procedure_result() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_6, b"5\n16\n");
+    assert_eq!(ProcedureResult::PolePairCprMismatch, odrive.read_procedure_result(AxisID::Zero).unwrap());
+    match odrive.read_procedure_result(AxisID::Zero) {
+        Err(ODriveError::InvalidMessageReceived(message, _)) => assert_eq!("16", message),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_active_errors_unsupported() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"");
+    assert!(matches!(odrive.read_active_errors(AxisID::Zero), Err(ODriveError::Unsupported(..))));
+    assert!(matches!(odrive.read_procedure_result(AxisID::Zero), Err(ODriveError::Unsupported(..))));
+    assert!(odrive.io_stream.get_mut().write_buffer.is_empty());
+}
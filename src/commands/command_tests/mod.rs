@@ -7,6 +7,9 @@ mod startup_tests;
 #[cfg(test)]
 mod configuration_tests;
 
+#[cfg(test)]
+mod config_tests;
+
 #[cfg(test)]
 mod base_tests;
 
@@ -19,7 +22,51 @@ mod motor_tests;
 #[cfg(test)]
 mod encoder_tests;
 
+#[cfg(test)]
+mod error_tests;
+
+#[cfg(test)]
+mod sync_tests;
+
+#[cfg(test)]
+mod response_tests;
+
+#[cfg(test)]
+mod property_tests;
+
+#[cfg(test)]
+mod tree_tests;
+
+#[cfg(test)]
+mod transaction_tests;
+
+#[cfg(test)]
+mod version_tests;
+
+#[cfg(test)]
+mod watchdog_tests;
+
+const FIRMWARE_0_4: FirmwareVersion = FirmwareVersion::new(0, 4, 12);
+const FIRMWARE_0_5: FirmwareVersion = FirmwareVersion::new(0, 5, 6);
+const FIRMWARE_0_6: FirmwareVersion = FirmwareVersion::new(0, 6, 10);
+
 fn init_odrive() -> ODrive<MockStream> {
-    let stream = MockStream::default();
+    let stream = MockStream::new();
     ODrive::new(stream)
-}
\ No newline at end of file
+}
+
+/// Creates an ODrive whose responses have all arrived already. As no answer to a write can still
+/// be on its way, it does not resynchronize after writes.
+fn init_odrive_with_responses(responses: &[u8]) -> ODrive<MockStream> {
+    let mut odrive = init_odrive();
+    odrive.set_sync_options(SyncOptions { resync_after_write: false, ..SyncOptions::default() });
+    odrive.io_stream.get_mut().read_buffer.extend(responses.iter().rev());
+    odrive
+}
+
+/// Creates an ODrive which already knows its firmware version, as if it was made with `connect`.
+fn init_odrive_with_firmware(firmware: FirmwareVersion, responses: &[u8]) -> ODrive<MockStream> {
+    let mut odrive = init_odrive_with_responses(responses);
+    odrive.firmware = Some(firmware);
+    odrive
+}
@@ -2,7 +2,7 @@ use super::*;
 
 #[test]
 fn test_set_pole_pairs() {
-    let mut odrive = init_odrive();
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"");
     odrive.set_motor_pole_pairs(AxisID::Zero, 25).unwrap();
     assert_eq!(b"w axis0.motor.config.pole_pairs 25\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
     assert!(odrive.io_stream.get_mut().flushed)
@@ -10,7 +10,7 @@ fn test_set_pole_pairs() {
 
 #[test]
 fn test_set_resistance_calibration_max_voltage() {
-    let mut odrive = init_odrive();
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"");
     odrive.set_motor_resistance_calib_max_voltage(AxisID::Zero, 25.0).unwrap();
     assert_eq!(b"w axis0.motor.config.resistance_calib_max_voltage 25\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
     assert!(odrive.io_stream.get_mut().flushed)
@@ -18,7 +18,7 @@ fn test_set_resistance_calibration_max_voltage() {
 
 #[test]
 fn test_set_requested_current_range() {
-    let mut odrive = init_odrive();
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"");
     odrive.set_motor_requested_current_range(AxisID::Zero, 25.0).unwrap();
     assert_eq!(b"w axis0.motor.config.requested_current_range 25\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
     assert!(odrive.io_stream.get_mut().flushed)
@@ -26,8 +26,85 @@ fn test_set_requested_current_range() {
 
 #[test]
 fn test_set_current_control_bandwidth() {
-    let mut odrive = init_odrive();
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"");
     odrive.set_motor_current_control_bandwidth(AxisID::Zero, 25.0).unwrap();
     assert_eq!(b"w axis0.motor.config.current_control_bandwidth 25\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
     assert!(odrive.io_stream.get_mut().flushed)
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_read_pole_pairs() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"15\n");
+    assert_eq!(15, odrive.read_motor_pole_pairs(AxisID::Zero).unwrap());
+    assert_eq!(b"r axis0.motor.config.pole_pairs\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_read_resistance_calibration_max_voltage() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"4.0\n");
+    assert_eq!(4.0, odrive.read_motor_resistance_calib_max_voltage(AxisID::Zero).unwrap());
+    assert_eq!(b"r axis0.motor.config.resistance_calib_max_voltage\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_read_requested_current_range() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"25.0\n");
+    assert_eq!(25.0, odrive.read_motor_requested_current_range(AxisID::One).unwrap());
+    assert_eq!(b"r axis1.motor.config.requested_current_range\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_read_current_control_bandwidth() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"100.0\n");
+    assert_eq!(100.0, odrive.read_motor_current_control_bandwidth(AxisID::Zero).unwrap());
+    assert_eq!(b"r axis0.motor.config.current_control_bandwidth\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_read_motor_pre_calibrated() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"1\n");
+    assert!(odrive.read_motor_pre_calibrated(AxisID::Zero).unwrap());
+    assert_eq!(b"r axis0.motor.config.pre_calibrated\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_read_motor_pre_calibrated_invalid() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"2\n");
+    match odrive.read_motor_pre_calibrated(AxisID::Zero) {
+        Err(ODriveError::InvalidMessageReceived(message, _)) => assert_eq!("2", message),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_set_motor_type() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"");
+    odrive.set_motor_type(AxisID::Zero, MotorType::Acim).unwrap();
+    assert_eq!(b"w axis0.motor.config.motor_type 3\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_set_motor_type_unsupported() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_4, b"");
+    match odrive.set_motor_type(AxisID::Zero, MotorType::Acim) {
+        Err(ODriveError::Unsupported(_, firmware)) => assert_eq!(FIRMWARE_0_4, firmware),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(odrive.io_stream.get_mut().write_buffer.is_empty());
+
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"");
+    assert!(odrive.set_motor_type(AxisID::Zero, MotorType::LowCurrent).is_err());
+    assert!(odrive.io_stream.get_mut().write_buffer.is_empty());
+}
+
+#[test]
+fn test_read_motor_type() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_6, b"2\n4\n");
+    assert_eq!(MotorType::MotorTypeGimbal, odrive.read_motor_type(AxisID::Zero).unwrap());
+    match odrive.read_motor_type(AxisID::Zero) {
+        Err(ODriveError::InvalidMessageReceived(message, _)) => assert_eq!("4", message),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(b"r axis0.config.motor.motor_type\nr axis0.config.motor.motor_type\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
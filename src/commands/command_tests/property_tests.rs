@@ -0,0 +1,80 @@
+use super::*;
+
+#[test]
+fn test_read_property_float() {
+    let mut odrive = init_odrive_with_responses(b"24.125\n");
+    let result: f32 = odrive.read_property("vbus_voltage").unwrap();
+    assert_eq!(24.125, result);
+    assert_eq!(b"r vbus_voltage\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_read_property_bool() {
+    let mut odrive = init_odrive_with_responses(b"1\n0\n");
+    assert!(odrive.read_axis_property::<bool>(AxisID::Zero, "motor.is_calibrated").unwrap());
+    assert!(!odrive.read_axis_property::<bool>(AxisID::Zero, "encoder.is_ready").unwrap());
+    assert_eq!(b"r axis0.motor.is_calibrated\nr axis0.encoder.is_ready\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_read_property_bool_out_of_range() {
+    let mut odrive = init_odrive_with_responses(b"2\n");
+    match odrive.read_axis_property::<bool>(AxisID::Zero, "motor.is_calibrated") {
+        Err(ODriveError::InvalidMessageReceived(message, _)) => assert_eq!("2", message),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_read_property_parse_failure() {
+    let mut odrive = init_odrive_with_responses(b"12.5\n");
+    match odrive.read_axis_property::<u16>(AxisID::One, "motor.config.pole_pairs") {
+        Err(ODriveError::InvalidMessageReceived(message, context)) => {
+            assert_eq!("12.5", message);
+            assert_eq!("r axis1.motor.config.pole_pairs", context.request);
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_read_property_unknown() {
+    let mut odrive = init_odrive_with_responses(b"invalid property\n");
+    match odrive.read_property::<f32>("vbus_voltag") {
+        Err(ODriveError::UnknownProperty(request)) => assert_eq!("r vbus_voltag", request),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_write_property() {
+    let mut odrive = init_odrive();
+    odrive.write_property("config.brake_resistance", 0.5).unwrap();
+    assert_eq!(b"w config.brake_resistance 0.5\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+    assert!(odrive.io_stream.get_mut().flushed)
+}
+
+#[test]
+fn test_write_axis_property_bool() {
+    let mut odrive = init_odrive();
+    odrive.write_axis_property(AxisID::One, "encoder.config.use_index", true).unwrap();
+    odrive.write_axis_property(AxisID::One, "config.enable_watchdog", false).unwrap();
+    assert_eq!(b"w axis1.encoder.config.use_index 1\nw axis1.config.enable_watchdog 0\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_axis_property_enum() {
+    let mut odrive = init_odrive_with_responses(b"8\n");
+    odrive.write_axis_property(AxisID::Zero, "requested_state", AxisState::Idle).unwrap();
+    odrive.write_axis_property(AxisID::Zero, "controller.config.control_mode", ControlMode::VelocityControl).unwrap();
+    assert_eq!(AxisState::ClosedLoopControl, odrive.read_axis_property(AxisID::Zero, "current_state").unwrap());
+    assert_eq!(b"w axis0.requested_state 1\nw axis0.controller.config.control_mode 2\nr axis0.current_state\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_read_property_string() {
+    let mut odrive = init_odrive_with_responses(b"0x3B7C\n");
+    assert_eq!("0x3B7C", odrive.read_property::<String>("serial_number").unwrap());
+}
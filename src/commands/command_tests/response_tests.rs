@@ -0,0 +1,108 @@
+use super::*;
+
+#[test]
+fn test_invalid_property() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"invalid property\n");
+    match odrive.get_velocity(AxisID::One) {
+        Err(ODriveError::UnknownProperty(request)) => assert_eq!("r axis1.encoder.vel_estimate", request),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_invalid_command_format() {
+    let mut odrive = init_odrive_with_responses(b"invalid command format\n");
+    writeln!(odrive, "r").unwrap();
+    match odrive.read_float() {
+        Err(ODriveError::MalformedCommand(request)) => assert_eq!("r", request),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_invalid_motor() {
+    let mut odrive = init_odrive_with_responses(b"invalid motor\n");
+    writeln!(odrive, "f 3").unwrap();
+    match odrive.read_odrive_response() {
+        Err(ODriveError::MalformedCommand(request)) => assert_eq!("f 3", request),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_unknown_command() {
+    let mut odrive = init_odrive_with_responses(b"unknown command\n");
+    write!(odrive, "sc").unwrap();
+    writeln!(odrive).unwrap();
+    match odrive.read_int() {
+        Err(ODriveError::UnknownCommand(request)) => assert_eq!("sc", request),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_read_string_returns_error_replies() {
+    let mut odrive = init_odrive_with_responses(b"invalid property\n");
+    assert_eq!("invalid property", odrive.read_string().unwrap().unwrap());
+}
+
+#[test]
+fn test_read_float_strict() {
+    let mut odrive = init_odrive_with_responses(b"abc\n");
+    match odrive.read_float() {
+        Err(ODriveError::InvalidMessageReceived(message, _)) => assert_eq!("abc", message),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_read_int_strict() {
+    let mut odrive = init_odrive_with_responses(b"2.5\n");
+    match odrive.read_int() {
+        Err(ODriveError::InvalidMessageReceived(message, _)) => assert_eq!("2.5", message),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_read_float_no_message() {
+    let mut odrive = init_odrive();
+    odrive.set_timeouts(Timeouts { read: Duration::from_millis(10), ..Timeouts::default() });
+    writeln!(odrive, "v 1 10 0").unwrap();
+    match odrive.read_float() {
+        Err(ODriveError::NoMessageReceived(context)) => {
+            assert_eq!("v 1 10 0", context.request);
+            assert_eq!(Some(AxisID::One), context.axis);
+            assert!(context.elapsed >= Duration::from_millis(10));
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_error_display() {
+    let context = RequestContext::new("r axis1.motor.error".to_owned(), Duration::from_millis(2));
+    let error = ODriveError::InvalidMessageReceived("abc".to_owned(), context);
+    assert_eq!("received invalid response `abc` to `r axis1.motor.error` on axis 1 after 2ms", error.to_string());
+
+    let context = RequestContext::new("r vbus_voltage".to_owned(), Duration::from_secs(1));
+    assert_eq!(None, context.axis);
+    assert_eq!("received no response to `r vbus_voltage` after 1s", ODriveError::NoMessageReceived(context).to_string());
+}
+
+#[test]
+fn test_error_source() {
+    use std::error::Error;
+
+    let error = ODriveError::Io(io::Error::from(ErrorKind::BrokenPipe));
+    assert!(error.source().is_some());
+    assert!(ODriveError::UnknownCommand("sc".to_owned()).source().is_none());
+
+    let error = ODriveError::RollbackFailed(Box::new(ODriveError::TransactionAborted("stop".to_owned())),
+                                            vec!["axis0.config.watchdog_timeout".to_owned()]);
+    assert_eq!("transaction aborted: stop, and could not restore: axis0.config.watchdog_timeout", error.to_string());
+    assert!(error.source().is_some());
+
+    let boxed: Box<dyn Error> = Box::new(ODriveError::UnknownProperty("r axis0.foo".to_owned()));
+    assert_eq!("unknown property in `r axis0.foo`", boxed.to_string());
+}
@@ -34,8 +34,114 @@ fn test_startup_closed_loop_control_setter() {
 
 #[test]
 fn test_startup_sensorless_control_setter() {
-    let mut odrive = init_odrive();
+    let mut odrive = init_odrive_with_firmware(FirmwareVersion::new(0, 4, 12), b"");
     odrive.set_startup_sensorless_control(AxisID::Zero, true).unwrap();
     assert_eq!(b"w axis0.config.startup_sensorless_control 1\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
     assert!(odrive.io_stream.get_mut().flushed)
 }
+
+#[test]
+fn test_startup_homing_setter() {
+    let mut odrive = init_odrive_with_firmware(FirmwareVersion::new(0, 5, 6), b"");
+    odrive.set_startup_homing(AxisID::One, true).unwrap();
+    assert_eq!(b"w axis1.config.startup_homing 1\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+    assert!(odrive.io_stream.get_mut().flushed)
+}
+
+#[test]
+fn test_startup_motor_calibration_reader() {
+    let mut odrive = init_odrive_with_responses(b"1\n");
+    assert!(odrive.read_startup_motor_calibration(AxisID::Zero).unwrap());
+    assert_eq!(b"r axis0.config.startup_motor_calibration\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_startup_reader_invalid() {
+    let mut odrive = init_odrive_with_responses(b"3\n");
+    match odrive.read_startup_closed_loop_control(AxisID::Zero) {
+        Err(ODriveError::InvalidMessageReceived(message, _)) => assert_eq!("3", message),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_read_startup_config() {
+    let mut odrive = init_odrive_with_firmware(FirmwareVersion::new(0, 5, 6), b"1\n0\n1\n1\n1\n");
+    let config = odrive.read_startup_config(AxisID::Zero).unwrap();
+    assert_eq!(StartupConfig {
+        motor_calibration: true,
+        encoder_index_search: false,
+        encoder_offset_calibration: true,
+        homing: true,
+        closed_loop_control: true,
+        sensorless_control: false,
+    }, config);
+    assert_eq!(b"r axis0.config.startup_motor_calibration\nr axis0.config.startup_encoder_index_search\n\
+                 r axis0.config.startup_encoder_offset_calibration\nr axis0.config.startup_homing\n\
+                 r axis0.config.startup_closed_loop_control\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_read_startup_config_without_homing() {
+    let mut odrive = init_odrive_with_firmware(FirmwareVersion::new(0, 4, 12), b"0\n0\n0\n0\n1\n");
+    let config = odrive.read_startup_config(AxisID::Zero).unwrap();
+    assert!(!config.homing);
+    assert!(config.sensorless_control);
+    assert!(!String::from_utf8(odrive.io_stream.get_mut().write_buffer.clone()).unwrap().contains("startup_homing"));
+}
+
+#[test]
+fn test_write_startup_config_changed_flags() {
+    // pre_calibrated flags, followed by the current startup configuration
+    let mut odrive = init_odrive_with_firmware(FirmwareVersion::new(0, 4, 12), b"1\n1\n0\n0\n0\n0\n0\n");
+    let config = StartupConfig { closed_loop_control: true, ..StartupConfig::default() };
+    odrive.write_startup_config(AxisID::One, &config).unwrap();
+    assert!(odrive.io_stream.get_mut().write_buffer.ends_with(
+        b"r axis1.config.startup_sensorless_control\nw axis1.config.startup_closed_loop_control 1\n"));
+}
+
+#[test]
+fn test_write_startup_config_unsupported_writes_nothing() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_4, b"1\n1\n0\n0\n0\n0\n0\n");
+    let config = StartupConfig { motor_calibration: true, homing: true, ..StartupConfig::default() };
+    match odrive.write_startup_config(AxisID::Zero, &config) {
+        Err(ODriveError::Unsupported(_, firmware)) => assert_eq!(FIRMWARE_0_4, firmware),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(!String::from_utf8(odrive.io_stream.get_mut().write_buffer.clone()).unwrap().contains("w "));
+}
+
+#[test]
+fn test_write_startup_config_uncalibrated_motor() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"0\n0\n");
+    let config = StartupConfig { closed_loop_control: true, ..StartupConfig::default() };
+    match odrive.write_startup_config(AxisID::Zero, &config) {
+        Err(ODriveError::InvalidConfiguration(_)) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(b"r axis0.motor.config.pre_calibrated\nr axis0.encoder.config.pre_calibrated\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_startup_config_validation() {
+    let closed_loop = StartupConfig { closed_loop_control: true, ..StartupConfig::default() };
+    assert!(closed_loop.validate(true, true).is_ok());
+    assert!(closed_loop.validate(true, false).is_err());
+    assert!(closed_loop.validate(false, true).is_err());
+
+    let calibrated = StartupConfig {
+        motor_calibration: true,
+        encoder_offset_calibration: true,
+        ..closed_loop
+    };
+    assert!(calibrated.validate(false, false).is_ok());
+
+    let both = StartupConfig { sensorless_control: true, ..calibrated };
+    assert!(both.validate(true, true).is_err());
+
+    let sensorless = StartupConfig { motor_calibration: true, sensorless_control: true, ..StartupConfig::default() };
+    assert!(sensorless.validate(false, false).is_ok());
+    assert!(StartupConfig::default().validate(false, false).is_ok());
+}
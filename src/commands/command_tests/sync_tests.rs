@@ -0,0 +1,107 @@
+use super::*;
+
+fn init_odrive_with_replies(replies: &[&[u8]]) -> ODrive<MockStream> {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"");
+    odrive.set_sync_options(SyncOptions::default());
+    odrive.set_timeouts(Timeouts { read: Duration::from_millis(10), ..Timeouts::default() });
+    odrive.io_stream.get_mut().replies.extend(replies.iter().map(|reply| reply.to_vec()));
+    odrive
+}
+
+#[test]
+fn test_late_response_discarded_after_timeout() {
+    let mut odrive = init_odrive_with_replies(&[b"", b"64\n24.5*29\n", b"1\n"]);
+    match odrive.read_axis_error(AxisID::Zero) {
+        Err(ODriveError::NoMessageReceived(context)) => assert_eq!("r axis0.error", context.request),
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    // The late response to the first request arrives before the next one is sent.
+    odrive.io_stream.get_mut().read_buffer.extend(b"64\n".iter().rev());
+
+    let result = odrive.read_motor_error(AxisID::Zero).unwrap();
+    assert_eq!(1, result.bits());
+    assert_eq!(b"r axis0.error\nr vbus_voltage*125\nr axis0.motor.error\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_stale_invalid_property_not_taken_for_sentinel() {
+    let mut odrive = init_odrive_with_replies(&[b"", b"invalid property\n24.5*30\ninvalid property*86\n", b"1\n"]);
+    assert!(odrive.read_property::<f32>("axis0.bad_property").is_err());
+
+    // The stale answer to the bad read, and a line with a wrong checksum, are both discarded.
+    assert_eq!(1, odrive.read_motor_error(AxisID::Zero).unwrap().bits());
+}
+
+#[test]
+fn test_resync_after_write() {
+    let mut odrive = init_odrive_with_replies(&[b"", b"invalid property\n24.5*29\n", b"1\n"]);
+    odrive.write_property("axis0.bad_property", 1).unwrap();
+
+    // The error answering the write arrives late, and is discarded rather than read as the error.
+    assert_eq!(1, odrive.read_motor_error(AxisID::Zero).unwrap().bits());
+    assert_eq!(b"w axis0.bad_property 1\nr vbus_voltage*125\nr axis0.motor.error\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_no_resync_after_write_when_disabled() {
+    let mut odrive = init_odrive_with_replies(&[b"", b"1\n"]);
+    odrive.set_sync_options(SyncOptions { resync_after_write: false, ..SyncOptions::default() });
+    odrive.write_property("axis0.motor.config.pole_pairs", 7).unwrap();
+    assert_eq!(1, odrive.read_motor_error(AxisID::Zero).unwrap().bits());
+    assert_eq!(b"w axis0.motor.config.pole_pairs 7\nr axis0.motor.error\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_no_resync_when_in_sync() {
+    let mut odrive = init_odrive_with_replies(&[b"64\n", b"1\n"]);
+    assert_eq!(64, odrive.read_axis_error(AxisID::Zero).unwrap().bits());
+    assert_eq!(1, odrive.read_motor_error(AxisID::Zero).unwrap().bits());
+    assert_eq!(b"r axis0.error\nr axis0.motor.error\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_drain_before_request() {
+    let mut odrive = init_odrive_with_replies(&[b"1\n"]);
+    odrive.set_sync_options(SyncOptions { drain_before_request: true, sentinel: false, ..SyncOptions::default() });
+    odrive.io_stream.get_mut().read_buffer.extend(b"64\n".iter().rev());
+    assert_eq!(1, odrive.read_axis_error(AxisID::Zero).unwrap().bits());
+    assert_eq!(b"r axis0.error\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_resync_without_sentinel_response() {
+    let mut odrive = init_odrive_with_replies(&[b""]);
+    match odrive.resync() {
+        Err(ODriveError::Desynchronized(_)) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_over_long_line() {
+    let mut odrive = init_odrive_with_replies(&[b"24.5*29\n", b"1\n"]);
+    odrive.io_stream.get_mut().read_buffer.extend([b'x'; 200].iter().chain(b"\n").rev());
+    match odrive.read_odrive_response() {
+        Err(ODriveError::Desynchronized(_)) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(1, odrive.read_axis_error(AxisID::One).unwrap().bits());
+}
+
+#[test]
+fn test_invalid_utf8() {
+    let mut odrive = init_odrive_with_replies(&[b"24.5*29\n", b"4\n"]);
+    odrive.io_stream.get_mut().read_buffer.extend(b"\xff\xfe\n".iter().rev());
+    match odrive.read_odrive_response() {
+        Err(ODriveError::Desynchronized(_)) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    odrive.io_stream.get_mut().read_buffer.extend(b"\xff\n".iter().rev());
+    assert_eq!(ErrorKind::InvalidData, odrive.read_string().unwrap_err().kind());
+
+    assert_eq!(4, odrive.read_encoder_error(AxisID::Zero).unwrap().bits());
+}
@@ -0,0 +1,111 @@
+use super::*;
+use std::panic::{self, AssertUnwindSafe};
+
+fn written(odrive: &mut ODrive<MockStream>) -> String {
+    String::from_utf8(odrive.io_stream.get_mut().write_buffer.clone()).unwrap()
+}
+
+#[test]
+fn test_transaction_commits() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"15\n");
+    let result = odrive.transaction(|odrive| {
+        odrive.set_motor_pole_pairs(AxisID::Zero, 7)?;
+        odrive.write_axis_property(AxisID::Zero, "controller.input_pos", 1)?;
+        Ok(3)
+    });
+    assert_eq!(3, result.unwrap());
+    assert_eq!("r axis0.motor.config.pole_pairs\nw axis0.motor.config.pole_pairs 7\nw axis0.controller.input_pos 1\n",
+               written(&mut odrive));
+    assert!(odrive.journal.is_none());
+}
+
+#[test]
+fn test_transaction_abort_restores_in_reverse() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"15\n0.5\n");
+    let result: ODriveResult<()> = odrive.transaction(|odrive| {
+        odrive.set_motor_pole_pairs(AxisID::Zero, 7)?;
+        odrive.set_encoder_bandwidth(AxisID::Zero, 100.0)?;
+        odrive.set_motor_pole_pairs(AxisID::Zero, 8)?;
+        Err(ODriveError::TransactionAborted("changed my mind".to_owned()))
+    });
+    match result {
+        Err(ODriveError::TransactionAborted(reason)) => assert_eq!("changed my mind", reason),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(written(&mut odrive).ends_with("w axis0.motor.config.pole_pairs 8\n\
+w axis0.encoder.config.bandwidth 0.5\nw axis0.motor.config.pole_pairs 15\n"));
+}
+
+#[test]
+fn test_transaction_error_restores() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"1\ninvalid property\n");
+    let result = odrive.transaction(|odrive| {
+        odrive.set_startup_closed_loop_control(AxisID::One, false)?;
+        odrive.read_property::<f32>("config.foo")
+    });
+    match result {
+        Err(ODriveError::UnknownProperty(request)) => assert_eq!("r config.foo", request),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(written(&mut odrive).ends_with("r config.foo\nw axis1.config.startup_closed_loop_control 1\n"));
+}
+
+#[test]
+fn test_nested_transaction_joins_outer() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"20\n");
+    let result: ODriveResult<()> = odrive.transaction(|odrive| {
+        odrive.transaction(|odrive| odrive.set_motor_current_limit(AxisID::Zero, 10.0))?;
+        Err(ODriveError::TransactionAborted("outer".to_owned()))
+    });
+    assert!(result.is_err());
+    assert!(written(&mut odrive).ends_with("w axis0.motor.config.current_lim 10\nw axis0.motor.config.current_lim 20\n"));
+}
+
+#[test]
+fn test_transaction_rollback_failed() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"15\n");
+    let result: ODriveResult<()> = odrive.transaction(|odrive| {
+        odrive.set_motor_pole_pairs(AxisID::Zero, 7)?;
+        odrive.io_stream.get_mut().write_error = Some(ErrorKind::BrokenPipe);
+        odrive.set_motor_pole_pairs(AxisID::Zero, 8)
+    });
+    match result {
+        Err(ODriveError::RollbackFailed(error, paths)) => {
+            assert!(matches!(*error, ODriveError::Io(_)));
+            assert_eq!(vec!["axis0.motor.config.pole_pairs".to_owned()], paths);
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_failed_nested_transaction_restores_its_changes() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"20\n10\n15\n");
+    let result = odrive.transaction(|odrive| {
+        odrive.set_motor_current_limit(AxisID::Zero, 10.0)?;
+        let inner: ODriveResult<()> = odrive.transaction(|odrive| {
+            odrive.set_motor_current_limit(AxisID::Zero, 15.0)?;
+            odrive.set_motor_pole_pairs(AxisID::Zero, 7)?;
+            Err(ODriveError::TransactionAborted("inner".to_owned()))
+        });
+        assert!(inner.is_err());
+        Ok(())
+    });
+    assert!(result.is_ok());
+    assert!(written(&mut odrive).ends_with("w axis0.motor.config.pole_pairs 7\n\
+w axis0.motor.config.pole_pairs 15\nw axis0.motor.config.current_lim 10\n"));
+    assert!(odrive.journal.is_none());
+}
+
+#[test]
+fn test_transaction_ends_on_panic() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"15\n");
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        odrive.transaction(|odrive| -> ODriveResult<()> {
+            odrive.set_motor_pole_pairs(AxisID::Zero, 7)?;
+            panic!("changes panicked");
+        })
+    }));
+    assert!(result.is_err());
+    assert!(odrive.journal.is_none());
+}
@@ -0,0 +1,128 @@
+use super::*;
+use crate::properties::{fw_0_4, fw_0_5, fw_0_6, ValueKind};
+
+#[test]
+fn test_tree_paths() {
+    let mut odrive = init_odrive();
+    assert_eq!("axis0.motor.config.current_lim", odrive.axis(AxisID::Zero).motor().config().current_lim().path());
+    assert_eq!("axis1.trap_traj.config.A_per_css", odrive.axis(AxisID::One).trap_traj().config().a_per_css().path());
+    assert_eq!("axis1.max_endstop.config.gpio_num", odrive.board().axis1().max_endstop().config().gpio_num().path());
+    assert_eq!("config.brake_resistance", odrive.board().config().brake_resistance().path());
+    assert_eq!("vbus_voltage", odrive.board().vbus_voltage().path());
+}
+
+#[test]
+fn test_tree_read_float() {
+    let mut odrive = init_odrive_with_responses(b"10.5\n");
+    let result = odrive.axis(AxisID::Zero).motor().config().current_lim().read().unwrap();
+    assert_eq!(10.5, result);
+    assert_eq!(b"r axis0.motor.config.current_lim\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_tree_read_bool() {
+    let mut odrive = init_odrive_with_responses(b"1\n");
+    assert!(odrive.axis(AxisID::One).encoder().is_ready().read().unwrap());
+    assert_eq!(b"r axis1.encoder.is_ready\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_tree_read_enum() {
+    let mut odrive = init_odrive_with_responses(b"8\n2\n");
+    assert_eq!(fw_0_4::AxisState::ClosedLoopControl, odrive.axis(AxisID::Zero).current_state().read().unwrap());
+    assert_eq!(fw_0_4::ControlMode::VelocityControl, odrive.axis(AxisID::Zero).controller().config().control_mode().read().unwrap());
+}
+
+#[test]
+fn test_tree_read_enum_unknown_value() {
+    let mut odrive = init_odrive_with_responses(b"42\n");
+    match odrive.axis(AxisID::Zero).current_state().read() {
+        Err(ODriveError::InvalidMessageReceived(message, context)) => {
+            assert_eq!("42", message);
+            assert_eq!("r axis0.current_state", context.request);
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_tree_read_errors() {
+    let mut odrive = init_odrive_with_responses(b"16\n");
+    let result = odrive.axis(AxisID::One).motor().error().read().unwrap();
+    assert!(result.contains(fw_0_4::MotorError::ControlDeadlineMissed));
+}
+
+#[test]
+fn test_tree_write() {
+    let mut odrive = init_odrive();
+    odrive.axis(AxisID::Zero).motor().config().current_lim().write(20.0).unwrap();
+    odrive.axis(AxisID::One).encoder().config().use_index().write(true).unwrap();
+    odrive.axis(AxisID::One).requested_state().write(fw_0_4::AxisState::Idle).unwrap();
+    odrive.board().config().brake_resistance().write(0.5).unwrap();
+    assert_eq!(b"w axis0.motor.config.current_lim 20\nw axis1.encoder.config.use_index 1\nw axis1.requested_state 1\nw config.brake_resistance 0.5\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_tree_read_modify_write() {
+    let mut odrive = init_odrive_with_responses(b"2.5\n");
+    let mut vel_limit = odrive.axis(AxisID::Zero).controller().config().vel_limit();
+    let value = vel_limit.read().unwrap();
+    vel_limit.write(value * 2.0).unwrap();
+    assert_eq!(b"r axis0.controller.config.vel_limit\nw axis0.controller.config.vel_limit 5\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_tree_flags_from_interface() {
+    let mut odrive = init_odrive_with_responses(b"4224\n");
+    let result = odrive.axis(AxisID::Zero).motor().error().read().unwrap();
+    assert!(result.contains(fw_0_4::MotorError::CurrentUnstable));
+    assert!(result.contains(fw_0_4::MotorError::ModulationMagnitude));
+    assert_eq!(0, result.unknown_bits());
+}
+
+#[test]
+fn test_tree_enum_values_from_interface() {
+    use std::convert::TryFrom;
+    assert_eq!(Ok(fw_0_4::EncoderMode::SpiAbsAeat), fw_0_4::EncoderMode::try_from(0x102));
+    assert_eq!(Ok(fw_0_5::MotorType::Acim), fw_0_5::MotorType::try_from(3));
+    assert_eq!(Err(1), fw_0_5::MotorType::try_from(1));
+}
+
+#[test]
+fn test_tree_other_firmware() {
+    let mut odrive = init_odrive_with_responses(b"5\n1\n");
+    let input_mode = fw_0_5::axis(&mut odrive, AxisID::One).controller().config().input_mode().read().unwrap();
+    assert_eq!(fw_0_5::InputMode::TrapTraj, input_mode);
+    let errors = fw_0_5::board(&mut odrive).error().read().unwrap();
+    assert!(errors.contains(fw_0_5::BoardError::ControlIterationMissed));
+    fw_0_5::axis(&mut odrive, AxisID::Zero).motor().config().current_lim_margin().write(8.0).unwrap();
+    assert_eq!(b"r axis1.controller.config.input_mode\nr error\nw axis0.motor.config.current_lim_margin 8\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_tree_firmware_0_6() {
+    let mut odrive = init_odrive_with_responses(b"4112\n6\n");
+    let errors = fw_0_6::axis(&mut odrive, AxisID::Zero).active_errors().read().unwrap();
+    assert!(errors.contains(fw_0_6::BoardError::BadConfig));
+    assert!(errors.contains(fw_0_6::BoardError::CurrentLimitViolation));
+    let result = fw_0_6::axis(&mut odrive, AxisID::Zero).procedure_result().read().unwrap();
+    assert_eq!(fw_0_6::ProcedureResult::PhaseResistanceOutOfRange, result);
+    assert_eq!("axis0.pos_vel_mapper.vel", fw_0_6::axis(&mut odrive, AxisID::Zero).pos_vel_mapper().vel().path());
+    assert_eq!("axis0.config.motor.current_soft_max",
+               fw_0_6::axis(&mut odrive, AxisID::Zero).config().motor().current_soft_max().path());
+}
+
+#[test]
+fn test_tree_firmware_0_6_encoders() {
+    let mut odrive = init_odrive();
+    assert_eq!("inc_encoder0.config.cpr", fw_0_6::board(&mut odrive).inc_encoder0().config().cpr().path());
+    fw_0_6::axis(&mut odrive, AxisID::Zero).config().load_encoder().write(fw_0_6::EncoderId::IncEncoder0).unwrap();
+    fw_0_6::board(&mut odrive).spi_encoder0().config().mode().write(fw_0_6::SpiEncoderMode::Ams).unwrap();
+    assert_eq!(b"w axis0.config.load_encoder 1\nw spi_encoder0.config.mode 2\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+    assert!(fw_0_6::BOARD_CONFIG.contains(&("inc_encoder0.config.cpr", ValueKind::Integer)));
+    assert!(fw_0_6::AXIS_CONFIG.contains(&("config.commutation_encoder", ValueKind::Integer)));
+}
@@ -0,0 +1,251 @@
+use super::*;
+use crate::enumerations::errors::ActiveError;
+
+#[test]
+fn test_connect_reads_firmware_version() {
+    let mut stream = MockStream::new();
+    stream.read_buffer.extend(b"0\n5\n6\n".iter().rev());
+    let mut odrive = ODrive::connect(stream).unwrap();
+    assert_eq!(b"r fw_version_major\nr fw_version_minor\nr fw_version_revision\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+    assert_eq!(FIRMWARE_0_5, odrive.firmware_version().unwrap());
+}
+
+#[test]
+fn test_firmware_detected_on_first_use() {
+    let mut odrive = init_odrive_with_responses(b"0\n5\n6\n");
+    odrive.set_control_mode(AxisID::Zero, ControlMode::VelocityControl).unwrap();
+    odrive.set_control_mode(AxisID::Zero, ControlMode::PositionControl).unwrap();
+    assert_eq!(b"r fw_version_major\nr fw_version_minor\nr fw_version_revision\n\
+                 w axis0.controller.config.control_mode 2\nw axis0.controller.config.input_mode 1\n\
+                 w axis0.controller.config.control_mode 3\nw axis0.controller.config.input_mode 1\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_set_trajectory_control_mode() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_4, b"");
+    odrive.set_control_mode(AxisID::One, ControlMode::TrajectoryControl).unwrap();
+    assert_eq!(b"w axis1.controller.config.control_mode 4\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"");
+    odrive.set_control_mode(AxisID::One, ControlMode::TrajectoryControl).unwrap();
+    assert_eq!(b"w axis1.controller.config.control_mode 3\nw axis1.controller.config.input_mode 5\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_read_trajectory_control_mode() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"3\n5\n3\n1\n");
+    assert_eq!(ControlMode::TrajectoryControl, odrive.read_control_mode(AxisID::Zero).unwrap());
+    assert_eq!(ControlMode::PositionControl, odrive.read_control_mode(AxisID::Zero).unwrap());
+    assert_eq!(b"r axis0.controller.config.control_mode\nr axis0.controller.config.input_mode\n\
+                 r axis0.controller.config.control_mode\nr axis0.controller.config.input_mode\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_read_control_mode_removed_value() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"4\n");
+    match odrive.read_control_mode(AxisID::Zero) {
+        Err(ODriveError::InvalidMessageReceived(message, _)) => assert_eq!("4", message),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_unsupported_startup_procedures() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_4, b"");
+    match odrive.set_startup_homing(AxisID::Zero, true) {
+        Err(error @ ODriveError::Unsupported(..)) => {
+            assert_eq!("startup homing is not supported by firmware 0.4.12", error.to_string())
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"");
+    match odrive.read_startup_sensorless_control(AxisID::Zero) {
+        Err(ODriveError::Unsupported(feature, firmware)) => {
+            assert_eq!("startup sensorless control", feature);
+            assert_eq!(FIRMWARE_0_5, firmware);
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(odrive.io_stream.get_mut().write_buffer.is_empty());
+}
+
+#[test]
+fn test_write_startup_config_unsupported_procedure() {
+    // pre_calibrated flags, followed by the current startup configuration
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"1\n1\n0\n0\n0\n0\n0\n");
+    let config = StartupConfig { motor_calibration: true, sensorless_control: true, ..StartupConfig::default() };
+    match odrive.write_startup_config(AxisID::Zero, &config) {
+        Err(ODriveError::Unsupported(feature, _)) => assert_eq!("startup sensorless control", feature),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_current_and_torque_setpoints() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"");
+    odrive.set_torque(AxisID::Zero, 0.5).unwrap();
+    assert_eq!(b"c 0 0.5\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+    let error = odrive.set_current(AxisID::Zero, 10.0).unwrap_err();
+    assert_eq!(ErrorKind::InvalidData, error.kind());
+
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_4, b"");
+    match odrive.set_torque(AxisID::Zero, 0.5) {
+        Err(ODriveError::Unsupported(feature, _)) => assert_eq!("torque setpoints", feature),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_request_sensorless_state() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_4, b"");
+    odrive.request_state(AxisID::Zero, AxisState::SensorlessControl).unwrap();
+    assert_eq!(b"w axis0.requested_state 5\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"");
+    assert!(matches!(odrive.request_state(AxisID::Zero, AxisState::SensorlessControl),
+                     Err(ODriveError::Unsupported(..))));
+}
+
+#[test]
+fn test_motor_current_limit() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"40\n");
+    odrive.set_motor_current_limit(AxisID::One, 20.0).unwrap();
+    assert_eq!(40.0, odrive.read_motor_current_limit(AxisID::One).unwrap());
+    assert_eq!(b"w axis1.motor.config.current_lim 20\nr axis1.motor.config.current_lim\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_firmware_0_6_paths() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_6, b"2.5\n7\n");
+    assert_eq!(2.5, odrive.get_velocity(AxisID::Zero).unwrap());
+    assert_eq!(7, odrive.read_motor_pole_pairs(AxisID::Zero).unwrap());
+    odrive.set_motor_current_limit(AxisID::Zero, 30.0).unwrap();
+    odrive.set_encoder_bandwidth(AxisID::Zero, 1000.0).unwrap();
+    assert_eq!(b"r axis0.pos_vel_mapper.vel\nr axis0.config.motor.pole_pairs\n\
+                 w axis0.config.motor.current_soft_max 30\nw axis0.config.encoder_bandwidth 1000\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_firmware_0_6_unsupported() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_6, b"");
+    assert!(matches!(odrive.read_encoder_cpr(AxisID::Zero), Err(ODriveError::Unsupported(..))));
+    assert!(matches!(odrive.set_motor_requested_current_range(AxisID::Zero, 60.0), Err(ODriveError::Unsupported(..))));
+    match odrive.read_axis_error(AxisID::Zero) {
+        Err(ODriveError::Unsupported(feature, _)) => assert_eq!("the axis error register", feature),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(odrive.io_stream.get_mut().write_buffer.is_empty());
+}
+
+#[test]
+fn test_firmware_0_6_clear_errors() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_6, b"4112\n16\n");
+    let result = odrive.clear_errors(AxisID::Zero).unwrap();
+    assert!(result.active.contains(ActiveError::BadConfig));
+    assert!(result.active.contains(ActiveError::CurrentLimitViolation));
+    assert_eq!(ActiveErrors::from_bits(16), result.disarm_reason);
+    assert!(result.axis.is_empty());
+    assert_eq!(b"r axis0.active_errors\nr axis0.disarm_reason\nsc\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_firmware_0_6_clear_all_errors() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_6, b"0\n16\n");
+    let result = odrive.clear_all_errors().unwrap();
+    assert_eq!(1, result.len());
+    assert_eq!(ActiveErrors::from_bits(16), result[0].disarm_reason);
+    assert_eq!(b"r axis0.active_errors\nr axis0.disarm_reason\nsc\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_firmware_0_6_change_state() {
+    // The procedure result is still busy on the first poll after the axis returned to idle.
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_6, b"1\n0\n1\n1\n0\n0\n");
+    let result = odrive.change_state(AxisID::Zero, AxisState::MotorCalibration).unwrap();
+    assert_eq!(StateOutcome::Reached, result);
+    assert_eq!(b"w axis0.requested_state 4\n\
+                 r axis0.current_state\nr axis0.requested_state\nr axis0.procedure_result\n\
+                 r axis0.current_state\nr axis0.requested_state\nr axis0.procedure_result\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_6, b"1\n0\n6\n");
+    let result = odrive.change_state(AxisID::Zero, AxisState::MotorCalibration).unwrap();
+    assert_eq!(StateOutcome::Aborted(ProcedureResult::PhaseResistanceOutOfRange), result);
+
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_6, b"1\n0\n3\n");
+    let result = odrive.change_state(AxisID::Zero, AxisState::ClosedLoopControl).unwrap();
+    assert_eq!(StateOutcome::Aborted(ProcedureResult::Disarmed), result);
+}
+
+#[test]
+fn test_firmware_0_6_write_startup_config() {
+    // No pre_calibrated flags to read, followed by the current startup configuration
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_6, b"0\n0\n0\n0\n0\n");
+    let config = StartupConfig { closed_loop_control: true, ..StartupConfig::default() };
+    odrive.write_startup_config(AxisID::Zero, &config).unwrap();
+    assert!(odrive.io_stream.get_mut().write_buffer.starts_with(b"r axis0.config.startup_motor_calibration\n"));
+    assert!(odrive.io_stream.get_mut().write_buffer.ends_with(b"w axis0.config.startup_closed_loop_control 1\n"));
+}
+
+#[test]
+fn test_dc_bus_trip_levels() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_6, b"56\n");
+    odrive.set_dc_bus_overvoltage_trip_level(52.0).unwrap();
+    odrive.set_dc_bus_undervoltage_trip_level(10.5).unwrap();
+    assert_eq!(56.0, odrive.read_dc_bus_overvoltage_trip_level().unwrap());
+    assert_eq!(b"w config.dc_bus_overvoltage_trip_level 52\nw config.dc_bus_undervoltage_trip_level 10.5\n\
+                 r config.dc_bus_overvoltage_trip_level\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_request_state_by_firmware() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_4, b"");
+    match odrive.request_state(AxisID::Zero, AxisState::Homing) {
+        Err(error @ ODriveError::Unsupported(..)) => {
+            assert_eq!("the Homing state is not supported by firmware 0.4.12", error.to_string())
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+    odrive.request_state(AxisID::Zero, AxisState::EncoderDirFind).unwrap();
+
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"");
+    odrive.request_state(AxisID::One, AxisState::EncoderHallPhaseCalibration).unwrap();
+    assert!(matches!(odrive.request_state(AxisID::One, AxisState::AnticoggingCalibration),
+                     Err(ODriveError::Unsupported(..))));
+    assert_eq!(b"w axis1.requested_state 13\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_request_state_without_firmware_check() {
+    // States which exist on every version do not need the firmware version.
+    let mut odrive = init_odrive();
+    odrive.request_state(AxisID::Zero, AxisState::LockinSpin).unwrap();
+    assert_eq!(b"w axis0.requested_state 9\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_run_state_unsupported() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_4, b"");
+    let error = odrive.run_state(AxisID::Zero, AxisState::Homing, false).unwrap_err();
+    assert_eq!(ErrorKind::InvalidData, error.kind());
+    assert!(odrive.io_stream.get_mut().write_buffer.is_empty());
+}
+
+#[test]
+fn test_axis_state_try_from() {
+    use std::convert::TryFrom;
+    assert_eq!(Ok(AxisState::EncoderHallPolarityCalibration), AxisState::try_from(12));
+    assert_eq!(Ok(AxisState::AnticoggingCalibration), AxisState::try_from(14));
+    assert_eq!(Err(15), AxisState::try_from(15));
+    assert!(AxisState::SensorlessControl.is_supported_by(FIRMWARE_0_4));
+    assert!(!AxisState::SensorlessControl.is_supported_by(FIRMWARE_0_5));
+}
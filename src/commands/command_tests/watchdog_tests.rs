@@ -0,0 +1,117 @@
+use super::*;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+#[test]
+fn test_feed_watchdog() {
+    let mut odrive = init_odrive();
+    odrive.feed_watchdog(AxisID::One).unwrap();
+    assert_eq!(b"u 1\n".to_vec(), odrive.io_stream.get_mut().write_buffer);
+    assert!(odrive.io_stream.get_mut().flushed)
+}
+
+#[test]
+fn test_set_watchdog_config() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_5, b"");
+    odrive.set_watchdog_timeout(AxisID::Zero, 0.5).unwrap();
+    odrive.set_watchdog_enabled(AxisID::Zero, true).unwrap();
+    assert_eq!(b"w axis0.config.watchdog_timeout 0.5\nw axis0.config.enable_watchdog 1\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_read_watchdog_config() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_6, b"0.25\n1\n");
+    assert_eq!(0.25, odrive.read_watchdog_timeout(AxisID::Zero).unwrap());
+    assert!(odrive.read_watchdog_enabled(AxisID::Zero).unwrap());
+    assert_eq!(b"r axis0.config.watchdog_timeout\nr axis0.config.enable_watchdog\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
+
+#[test]
+fn test_set_watchdog_enabled_0_4() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_4, b"");
+    match odrive.set_watchdog_enabled(AxisID::Zero, true) {
+        Err(ODriveError::Unsupported(_, firmware)) => assert_eq!(FIRMWARE_0_4, firmware),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(odrive.io_stream.get_mut().write_buffer.is_empty());
+}
+
+#[test]
+fn test_read_watchdog_enabled_0_4() {
+    let mut odrive = init_odrive_with_firmware(FIRMWARE_0_4, b"0.5\n0\n");
+    assert!(odrive.read_watchdog_enabled(AxisID::Zero).unwrap());
+    assert!(!odrive.read_watchdog_enabled(AxisID::One).unwrap());
+    assert_eq!(b"r axis0.config.watchdog_timeout\nr axis1.config.watchdog_timeout\n".to_vec(),
+               odrive.io_stream.get_mut().write_buffer);
+}
+
+/// Polls `condition` until it holds, failing the test if it does not within a generous deadline.
+fn wait_until(mut condition: impl FnMut() -> bool) {
+    let timer = Instant::now();
+    while !condition() {
+        assert!(timer.elapsed() < Duration::from_secs(10), "condition not reached");
+        sleep(Duration::from_millis(1));
+    }
+}
+
+fn count_feeds(odrive: &Arc<Mutex<ODrive<MockStream>>>) -> usize {
+    let mut odrive = odrive.lock().unwrap();
+    let written = String::from_utf8(odrive.io_stream.get_mut().write_buffer.clone()).unwrap();
+    written.matches("u 0\n").count()
+}
+
+#[test]
+fn test_keep_alive_feeds_until_dropped() {
+    let odrive = Arc::new(Mutex::new(init_odrive()));
+    let keep_alive = WatchdogKeepAlive::with_interval(Arc::clone(&odrive), &[AxisID::Zero], Duration::from_millis(1));
+    wait_until(|| count_feeds(&odrive) >= 2);
+    assert!(keep_alive.is_running());
+    drop(keep_alive);
+
+    // Dropping joins the thread, so no feed can follow.
+    let feeds = count_feeds(&odrive);
+    thread::yield_now();
+    assert_eq!(feeds, count_feeds(&odrive));
+}
+
+#[test]
+fn test_keep_alive_interval_from_timeout() {
+    let odrive = Arc::new(Mutex::new(init_odrive_with_responses(b"0.4\n0.2\n")));
+    let keep_alive = WatchdogKeepAlive::start(Arc::clone(&odrive), &[AxisID::Zero, AxisID::One]).unwrap();
+    assert_eq!(Duration::from_millis(50), keep_alive.interval());
+}
+
+#[test]
+fn test_keep_alive_disabled_timeout() {
+    let odrive = Arc::new(Mutex::new(init_odrive_with_responses(b"0\n")));
+    match WatchdogKeepAlive::start(Arc::clone(&odrive), &[AxisID::Zero]) {
+        Err(ODriveError::InvalidConfiguration(_)) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(0, count_feeds(&odrive));
+}
+
+#[test]
+fn test_keep_alive_infinite_timeout() {
+    let odrive = Arc::new(Mutex::new(init_odrive_with_responses(b"inf\n")));
+    match WatchdogKeepAlive::start(Arc::clone(&odrive), &[AxisID::Zero]) {
+        Err(ODriveError::InvalidConfiguration(_)) => {}
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(0, count_feeds(&odrive));
+}
+
+#[test]
+fn test_keep_alive_stops_when_poisoned() {
+    let odrive = Arc::new(Mutex::new(init_odrive()));
+    let poisoner = Arc::clone(&odrive);
+    let _ = thread::spawn(move || {
+        let _guard = poisoner.lock().unwrap();
+        panic!("poisoning the ODrive");
+    }).join();
+
+    let keep_alive = WatchdogKeepAlive::with_interval(Arc::clone(&odrive), &[AxisID::Zero], Duration::from_millis(1));
+    wait_until(|| !keep_alive.is_running());
+}
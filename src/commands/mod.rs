@@ -1,38 +1,212 @@
-use std::fmt::Display;
-use std::io::{BufReader, Error, Read, Write};
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::{BufRead, BufReader, Error, ErrorKind, Read, Write};
 use std::io;
-use std::time::Instant;
+use std::thread::sleep;
+use std::time::{Duration, Instant};
 
-use crate::enumerations::{AxisID, AxisState, ControlMode, EncoderMode};
-use crate::enumerations::errors::{ODriveError, ODriveResult};
+use crate::enumerations::{AxisID, AxisState, ControlMode, EncoderMode, MotorType, ProcedureResult};
+use crate::enumerations::errors::{ActiveErrors, AxisErrorReport, AxisErrors, ControllerErrors, EncoderErrors,
+                                  ErrorFlag, ErrorSet, MotorErrors, ODriveError, ODriveResult, RequestContext};
+use crate::firmware::FirmwareVersion;
+use crate::properties::PropertyValue;
+use crate::properties::fw_0_5::InputMode;
+
+use self::paths::VersionedPath;
+
+mod paths;
+mod watchdog;
+
+pub use self::watchdog::WatchdogKeepAlive;
 
 #[cfg(test)]
 #[cfg_attr(tarpaulin, skip)]
 mod command_tests;
 
+/// Converts an error for functions which predate `ODriveError` and return `io::Result`.
+fn into_io_error(error: ODriveError) -> io::Error {
+    match error {
+        ODriveError::Io(error) => error,
+        other => io::Error::new(ErrorKind::InvalidData, other),
+    }
+}
+
+/// The result of waiting for an axis to finish a state transition.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum StateOutcome {
+    /// The axis reached the requested state. For states which run to completion, this means the
+    /// procedure finished and the axis returned to `Idle` without errors.
+    Reached,
+    /// The axis fell back to `Idle`, with the given errors set on `<axis>.error`.
+    ReturnedToIdle(AxisErrors),
+    /// The axis fell back to `Idle` on firmware 0.6 and later, which report the outcome of a state
+    /// through `<axis>.procedure_result` rather than error registers. For states which should have
+    /// kept running, such as closed loop control, the result is `Disarmed`.
+    Aborted(ProcedureResult),
+    /// The transition did not finish before the timeout.
+    TimedOut,
+}
+
+/// How long to wait before polling a stream again after it returned no data.
+const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// How long to wait before trying to reopen a connection again.
+const RECONNECT_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Deadlines used by blocking operations on an `ODrive`.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Timeouts {
+    /// How long to wait for a response to a single request. Defaults to 1 second.
+    pub read: Duration,
+    /// How long the blocking state machine helpers, such as `change_state`, wait for a transition
+    /// to finish. Defaults to 10 seconds.
+    pub state_change: Duration,
+    /// How long `reconnect` keeps trying to reopen the connection. Defaults to 10 seconds.
+    pub reconnect: Duration,
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Self {
+            read: Duration::from_millis(1_000),
+            state_change: Duration::from_millis(10_000),
+            reconnect: Duration::from_millis(10_000),
+        }
+    }
+}
+
+/// The request sent by `ODrive::resync` to mark the end of stale input. It is sent with a checksum,
+/// so the ODrive answers with a checksum too, which a response to any other request never has.
+const SENTINEL_REQUEST: &str = "r vbus_voltage";
+
+/// The checksum of the ASCII protocol, the XOR of every byte before the `*`.
+fn checksum(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0, |checksum, byte| checksum ^ byte)
+}
+
+/// Returns true if the line ends with a valid checksum, such as `24.1*29`.
+fn has_valid_checksum(line: &str) -> bool {
+    match line.rfind('*') {
+        Some(start) => line[start + 1..].parse() == Ok(checksum(&line.as_bytes()[..start])),
+        None => false,
+    }
+}
+
+/// Controls how an `ODrive` keeps its requests and the responses to them aligned.
+///
+/// A response which arrives after its request timed out would otherwise be read as the response
+/// to the next request, shifting every response after it by one.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct SyncOptions {
+    /// Discard unread input before every request, rather than only after a timeout or a malformed
+    /// response. Defaults to `false`.
+    pub drain_before_request: bool,
+    /// After discarding input, send a checksummed request and discard everything up to its
+    /// checksummed response, so that responses still in flight are discarded too. Defaults to
+    /// `true`.
+    pub sentinel: bool,
+    /// Resynchronize before the next request after a write. The ODrive only answers a write when
+    /// it fails, and such an answer would otherwise be read as the response to the next request.
+    /// Defaults to `true`.
+    pub resync_after_write: bool,
+    /// Responses longer than this many bytes are treated as garbage. Defaults to 128.
+    pub max_line_length: usize,
+}
+
+impl Default for SyncOptions {
+    fn default() -> Self {
+        Self {
+            drain_before_request: false,
+            sentinel: true,
+            resync_after_write: true,
+            max_line_length: 128,
+        }
+    }
+}
+
 /// The `ODrive` struct manages a connection with an ODrive motor over the ASCII protocol.
 /// It acts as a newtype around a connection stream.
 /// This has been tested using serial types from `serialport-rs`.
 #[derive(Debug)]
 pub struct ODrive<T> where T: Read {
     io_stream: BufReader<T>,
+    firmware: Option<FirmwareVersion>,
+    timeouts: Timeouts,
+    sync: SyncOptions,
+    /// Set when unread or partially read input may be left on the stream.
+    stale: bool,
+    /// The part of the current line which has been written so far.
+    partial_request: Vec<u8>,
+    /// The last complete line written, used to report which request an error belongs to.
+    last_request: String,
+    /// When `last_request` was written.
+    last_request_sent: Instant,
+    /// The previous values of the configuration properties written during a transaction, in the
+    /// order they were first written.
+    journal: Option<Vec<(String, String)>>,
+    /// The index of `journal` at which the innermost running transaction started.
+    journal_start: usize,
 }
 
 impl<T> ODrive<T> where T: Read {
     /// Although any type can be passed in here, it is suggested that the supplied type `T` be
     /// `Read + Write`. Doing so will unlock the full API.
     pub fn new(io_stream: T) -> Self {
+        Self::with_timeouts(io_stream, Timeouts::default())
+    }
+
+    /// Creates a connection which uses the given timeouts instead of the defaults.
+    pub fn with_timeouts(io_stream: T, timeouts: Timeouts) -> Self {
         Self {
-            io_stream: BufReader::new(io_stream)
+            io_stream: BufReader::new(io_stream),
+            firmware: None,
+            timeouts,
+            sync: SyncOptions::default(),
+            stale: false,
+            partial_request: Vec::new(),
+            last_request: String::new(),
+            last_request_sent: Instant::now(),
+            journal: None,
+            journal_start: 0,
         }
     }
+
+    /// Returns the timeouts currently used by this connection.
+    pub fn timeouts(&self) -> Timeouts {
+        self.timeouts
+    }
+
+    /// Changes the timeouts used by this connection.
+    pub fn set_timeouts(&mut self, timeouts: Timeouts) {
+        self.timeouts = timeouts;
+    }
+
+    /// Returns the options used to keep requests and responses aligned.
+    pub fn sync_options(&self) -> SyncOptions {
+        self.sync
+    }
+
+    /// Changes the options used to keep requests and responses aligned.
+    pub fn set_sync_options(&mut self, sync: SyncOptions) {
+        self.sync = sync;
+    }
 }
 
 /// An implementation of `Write` has been provided as an escape hatch to enable the usage of
 /// operations not yet supported by this library.
 impl<T> Write for ODrive<T> where T: Write + Read {
     fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
-        self.io_stream.get_mut().write(buf)
+        let written = self.io_stream.get_mut().write(buf)?;
+        for &byte in &buf[..written] {
+            if byte == b'\n' {
+                self.last_request = String::from_utf8_lossy(&self.partial_request).trim().to_owned();
+                self.last_request_sent = Instant::now();
+                self.partial_request.clear();
+            } else {
+                self.partial_request.push(byte);
+            }
+        }
+        Ok(written)
     }
 
     fn flush(&mut self) -> Result<(), Error> {
@@ -51,70 +225,157 @@ impl<T> Read for ODrive<T> where T: Read {
 
 impl<T> ODrive<T> where T: Read {
     /// Reads the next message sent by the ODrive as a string.
-    /// If their is no message, this function should return `None`
+    /// If their is no message before the read timeout, this function should return `None`.
     ///
     /// It is suggested that you only use this if you are directly using the `Write` implementation
     /// and are expecting a response, as normally the supplied for the ODrive can directly support
     /// reading any response.
     pub fn read_string(&mut self) -> io::Result<Option<String>> {
-        let mut string = String::with_capacity(20);
-        let duration = Instant::now();
+        match self.read_line() {
+            Ok(line) => Ok(Some(line)),
+            Err(ODriveError::NoMessageReceived(_)) => Ok(None),
+            Err(error) => Err(into_io_error(error)),
+        }
+    }
+
+    /// Reads the next message sent by the ODrive, returning `ODriveError::NoMessageReceived` if
+    /// there is no message before the read timeout.
+    ///
+    /// If the message is one of the ODrive's error replies, such as `invalid property`, the
+    /// matching error is returned instead, carrying the last request sent.
+    pub fn read_odrive_response(&mut self) -> ODriveResult<String> {
+        let response = self.read_line()?;
+        match response.as_str() {
+            "invalid property" => Err(ODriveError::UnknownProperty(self.last_request.clone())),
+            "invalid command format" | "invalid motor" => Err(ODriveError::MalformedCommand(self.last_request.clone())),
+            "unknown command" => Err(ODriveError::UnknownCommand(self.last_request.clone())),
+            _ => Ok(response),
+        }
+    }
+
+    /// Reads a single newline-terminated line from the stream.
+    ///
+    /// Streams with their own read timeout, such as serial ports, block inside `read` and report
+    /// `TimedOut` or `WouldBlock` when no data arrived. Streams which instead return no data
+    /// immediately are polled every `POLL_INTERVAL` until the read timeout passes.
+    ///
+    /// If the line times out, is too long or is not valid UTF-8, the connection is marked as stale
+    /// so that it is resynchronized before the next request.
+    fn read_line(&mut self) -> ODriveResult<String> {
+        let timer = Instant::now();
+        let mut line = Vec::with_capacity(20);
         loop {
-            let mut buffer = [0; 1];
-            while self.read(&mut buffer).unwrap_or_default() == 0 {
-                if duration.elapsed().as_millis() >= 1_000 {
-                    return Ok(None);
+            // Read at most one buffer at a time, so that the deadline is checked even while a peer
+            // which trickles bytes keeps the stream from ever running dry.
+            let (complete, read) = match self.io_stream.fill_buf() {
+                Ok(available) => match available.iter().position(|&byte| byte == b'\n') {
+                    Some(end) => {
+                        line.extend_from_slice(&available[..=end]);
+                        (true, end + 1)
+                    }
+                    None => {
+                        line.extend_from_slice(available);
+                        (false, available.len())
+                    }
+                },
+                Err(ref error) if error.kind() == ErrorKind::Interrupted => continue,
+                Err(ref error) if error.kind() == ErrorKind::TimedOut => (false, 0),
+                Err(ref error) if error.kind() == ErrorKind::WouldBlock => (false, 0),
+                Err(error) => {
+                    self.stale = true;
+                    return Err(ODriveError::Io(error));
                 }
+            };
+            self.io_stream.consume(read);
+
+            if line.len() > self.sync.max_line_length {
+                self.stale = true;
+                return Err(ODriveError::Desynchronized(
+                    format!("received a line longer than {} bytes", self.sync.max_line_length)));
             }
-            let ch = buffer[0];
-            if ch as char == '\n' {
+            if complete {
                 break;
             }
+            // Part of a line may have arrived, so try again before sleeping.
+            if read > 0 && timer.elapsed() < self.timeouts.read {
+                continue;
+            }
 
-            string.push(ch as char);
+            if timer.elapsed() >= self.timeouts.read {
+                // The response may still arrive, and would then be read by the next request.
+                self.stale = true;
+                return Err(ODriveError::NoMessageReceived(self.request_context()));
+            }
+            sleep(POLL_INTERVAL);
         }
 
-        Ok(Some(string.trim().to_owned()))
+        match String::from_utf8(line) {
+            Ok(line) => Ok(line.trim().to_owned()),
+            Err(_) => {
+                self.stale = true;
+                Err(ODriveError::Desynchronized("received a line which is not valid UTF-8".to_owned()))
+            }
+        }
     }
 
-    pub fn read_odrive_response(&mut self) -> ODriveResult<String> {
-        let mut string = String::with_capacity(20);
-        let duration = Instant::now();
-        loop {
-            let mut buffer = [0; 1];
-            while self.read(&mut buffer).unwrap_or_default() == 0 {
-                if duration.elapsed().as_millis() >= 1_000 {
-                    return Err(ODriveError::NoMessageReceived);
-                }
-            }
-            let ch = buffer[0];
-            if ch as char == '\n' {
-                break;
-            }
+    /// Describes the last request sent, for attaching to errors.
+    fn request_context(&self) -> RequestContext {
+        RequestContext::new(self.last_request.clone(), self.last_request_sent.elapsed())
+    }
+
+    pub(crate) fn invalid_message(&self, response: String) -> ODriveError {
+        ODriveError::InvalidMessageReceived(response, self.request_context())
+    }
 
-            string.push(ch as char);
+    /// Parses a response with the representation of the ASCII protocol, see `PropertyValue`.
+    fn parse_response<V: PropertyValue>(&self, response: String) -> ODriveResult<V> {
+        match V::parse(&response) {
+            Some(value) => Ok(value),
+            None => Err(self.invalid_message(response)),
         }
+    }
 
-        Ok(string.trim().to_owned())
+    /// Discards all input which has already been received, without waiting for more.
+    fn drain_input(&mut self) -> ODriveResult<()> {
+        let buffered = self.io_stream.buffer().len();
+        self.io_stream.consume(buffered);
+
+        // A device which never stops sending would otherwise keep us here forever.
+        let timer = Instant::now();
+        let mut scratch = [0; 64];
+        while timer.elapsed() < self.timeouts.read {
+            match self.io_stream.get_mut().read(&mut scratch) {
+                Ok(0) => break,
+                Ok(_) => {}
+                Err(ref error) if error.kind() == ErrorKind::Interrupted => {}
+                Err(ref error) if error.kind() == ErrorKind::TimedOut => break,
+                Err(ref error) if error.kind() == ErrorKind::WouldBlock => break,
+                Err(error) => return Err(ODriveError::Io(error)),
+            }
+        }
+        Ok(())
     }
 
-    /// Reads the next message as a float. This will return zero if the message is not a valid
-    /// float.
+    /// Reads the next message as a float. This will return `ODriveError::InvalidMessageReceived`
+    /// if the message is not a valid float.
     ///
     /// It is suggested that you only use this if you are directly using the `Write` implementation
     /// and are expecting a response, as normally the supplied for the ODrive can directly support
     /// reading any response.
-    pub fn read_float(&mut self) -> io::Result<Option<f32>> {
-        Ok(self.read_string()?.map(|s| s.parse().unwrap_or_default()))
+    pub fn read_float(&mut self) -> ODriveResult<f32> {
+        let response = self.read_odrive_response()?;
+        self.parse_response(response)
     }
 
-    /// Reads the next message as an int. This will return zero if the message is not a valid int.
+    /// Reads the next message as an int. This will return `ODriveError::InvalidMessageReceived`
+    /// if the message is not a valid int.
     ///
     /// It is suggested that you only use this if you are directly using the `Write` implementation
     /// and are expecting a response, as normally the supplied for the ODrive can directly support
     /// reading any response.
-    pub fn read_int(&mut self) -> io::Result<Option<i32>> {
-        Ok(self.read_string()?.map(|s| s.parse().unwrap_or_default()))
+    pub fn read_int(&mut self) -> ODriveResult<i32> {
+        let response = self.read_odrive_response()?;
+        self.parse_response(response)
     }
 }
 
@@ -122,8 +383,9 @@ impl<T> ODrive<T> where T: Write + Read {
     /// Move the motor to a position. Use this command if you have a real-time controller which
     /// is streaming setpoints and tracking a trajectory.
     /// `axis` The motor to be used for the operation.
-    /// `position` is the desired position, in encoder counts.
-    /// `velocity_feed_forward` is the velocity feed forward term, in encoder counts per second.
+    /// `position` is the desired position, in encoder counts (turns on firmware 0.5 and later).
+    /// `velocity_feed_forward` is the velocity feed forward term, in encoder counts per second
+    /// (turns per second on firmware 0.5 and later).
     /// `current_feed_forward` is the current feed forward term, in amps.
     /// If `None` is supplied for a feed forward input, zero will be provided as a default.
     pub fn set_position_p(&mut self, axis: AxisID, position: f32, velocity_feed_forward: Option<f32>,
@@ -136,8 +398,9 @@ impl<T> ODrive<T> where T: Write + Read {
 
     /// Move the motor to a position. Use this command if you are sending one setpoint at a time.
     /// `axis` The motor to be used for the operation.
-    /// `position` is the desired position, in encoder counts.
-    /// `velocity_limit` is the velocity limit, in encoder counts per second.
+    /// `position` is the desired position, in encoder counts (turns on firmware 0.5 and later).
+    /// `velocity_limit` is the velocity limit, in encoder counts per second (turns per second on
+    /// firmware 0.5 and later).
     /// `current_limit` is the current limit, in amps.
     /// If `None` is supplied for a limit, zero will be provided as a default.
     pub fn set_position_q(&mut self, axis: AxisID, position: f32, velocity_limit: Option<f32>,
@@ -150,7 +413,8 @@ impl<T> ODrive<T> where T: Write + Read {
 
     /// Specifies a velocity setpoint for the motor.
     /// `axis` The motor to be used for the operation.
-    /// `velocity` is the velocity setpoint, in encoder counts per second.
+    /// `velocity` is the velocity setpoint, in encoder counts per second (turns per second on
+    /// firmware 0.5 and later).
     /// `current_feed_forward` is the current feed forward term, in amps.
     /// If `None` is supplied for a feed forward input, zero will be provided as a default.
     pub fn set_velocity(&mut self, axis: AxisID, velocity: f32, current_feed_forward: Option<f32>) -> io::Result<()> {
@@ -159,40 +423,101 @@ impl<T> ODrive<T> where T: Write + Read {
         self.flush()
     }
 
-    /// Specifies a velocity setpoint for the motor.
+    /// Specifies a current setpoint for the motor.
     /// `axis` The motor to be used for the operation.
     /// `current` is the current to be supplied, in amps.
+    ///
+    /// Firmware 0.5 and later are commanded in torque instead, see `set_torque`. On those versions
+    /// this fails with `ODriveError::Unsupported`, wrapped in an `io::Error`.
     pub fn set_current(&mut self, axis: AxisID, current: f32) -> io::Result<()> {
+        let firmware = self.firmware_version().map_err(into_io_error)?;
+        if firmware.uses_torque_setpoints() {
+            return Err(into_io_error(ODriveError::Unsupported("current setpoints".to_owned(), firmware)));
+        }
         writeln!(self, "c {} {}", axis as u8, current)?;
         self.flush()
     }
 
+    /// Specifies a torque setpoint for the motor, on firmware 0.5 and later.
+    /// `axis` The motor to be used for the operation.
+    /// `torque` is the torque to be applied, in Nm.
+    ///
+    /// Older firmware is commanded in current instead, see `set_current`. On those versions this
+    /// fails with `ODriveError::Unsupported`.
+    pub fn set_torque(&mut self, axis: AxisID, torque: f32) -> ODriveResult<()> {
+        let firmware = self.firmware_version()?;
+        if !firmware.uses_torque_setpoints() {
+            return Err(ODriveError::Unsupported("torque setpoints".to_owned(), firmware));
+        }
+        writeln!(self, "c {} {}", axis as u8, torque).map_err(ODriveError::Io)?;
+        self.flush().map_err(ODriveError::Io)
+    }
+
     /// Moves a motor to a given position
     /// For general movement, this is the best command.
     /// `axis` The motor to be used for the operation.
-    /// `position` is the desired position, in encoder counts.
+    /// `position` is the desired position, in encoder counts (turns on firmware 0.5 and later).
     pub fn set_trajectory(&mut self, axis: AxisID, position: f32) -> io::Result<()> {
         writeln!(self, "t {} {}", axis as u8, position)?;
         self.flush()
     }
 }
 
+/// The position and velocity estimates of an axis, as returned by `ODrive::get_feedback`.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Feedback {
+    /// The position estimate, in encoder counts (turns on firmware 0.5 and later).
+    pub pos: f32,
+    /// The velocity estimate, in counts per second (turns per second on firmware 0.5 and later).
+    pub vel: f32,
+    /// When the request was written to the stream.
+    pub sent_at: Instant,
+    /// When the response was read from the stream. The estimates were taken at some point between
+    /// `sent_at` and `received_at`.
+    pub received_at: Instant,
+}
+
+impl Feedback {
+    /// The time between sending the request and receiving the response.
+    pub fn latency(&self) -> Duration {
+        self.received_at.duration_since(self.sent_at)
+    }
+}
+
 impl<T> ODrive<T> where T: Read + Write {
-    /// Retrieves the velocity of a motor, in counts per second.
-    pub fn get_velocity(&mut self, axis: AxisID) -> io::Result<Option<f32>> {
-        writeln!(self, "r axis{} .encoder.vel_estimate", axis as u8)?;
-        self.flush()?;
-        self.read_float()
+    /// Retrieves the position and velocity estimates of a motor in a single request, using the
+    /// `f` command.
+    pub fn get_feedback(&mut self, axis: AxisID) -> ODriveResult<Feedback> {
+        self.begin_request()?;
+        writeln!(self, "f {}", axis as u8).map_err(ODriveError::Io)?;
+        self.flush().map_err(ODriveError::Io)?;
+        let sent_at = self.last_request_sent;
+        let response = self.read_odrive_response()?;
+        let received_at = Instant::now();
+
+        let mut values = response.split_whitespace().map(str::parse::<f32>);
+        match (values.next(), values.next(), values.next()) {
+            (Some(Ok(pos)), Some(Ok(vel)), None) => Ok(Feedback { pos, vel, sent_at, received_at }),
+            _ => Err(self.invalid_message(response)),
+        }
+    }
+
+    /// Retrieves the velocity of a motor, in counts per second (turns per second on firmware 0.5
+    /// and later).
+    pub fn get_velocity(&mut self, axis: AxisID) -> ODriveResult<f32> {
+        let path = self.versioned_path(&paths::VELOCITY_ESTIMATE)?;
+        self.read_axis_property(axis, path)
     }
 
     /// Changes the state of an axis.
     /// The `wait` flag indicates whether this command should block until the state is updated.
     /// Returns true unless we are in blocking mode and the operation times out.
-    /// The current timeout is 10 seconds.
-    ///
-    /// This command will likely be deprecated and reworked in a future release.
+    /// The timeout is `Timeouts::state_change`.
+    #[deprecated(note = "use `request_state` or `change_state`, which wait for the requested state and report failures")]
     pub fn run_state(&mut self, axis: AxisID, requested_state: AxisState, wait: bool) -> io::Result<bool> {
+        let timeout = self.timeouts.state_change;
         let timer = Instant::now();
+        self.check_state_supported(requested_state).map_err(into_io_error)?;
         writeln!(self, "w axis{}.requested_state {}", axis as u8, requested_state as u8)?;
         self.flush()?;
         if wait {
@@ -200,29 +525,484 @@ impl<T> ODrive<T> where T: Read + Write {
                 writeln!(self, "r axis{}.current_state", axis as u8)?;
                 self.flush()?;
 
-                self.read_int()?.unwrap_or_default() != AxisState::Idle as i32
-                    && timer.elapsed().as_millis() < 10_000 // exit
+                let current_state = match self.read_int() {
+                    Ok(state) => state,
+                    Err(ODriveError::Io(error)) => return Err(error),
+                    Err(_) => 0,
+                };
+
+                current_state != AxisState::Idle as i32
+                    && timer.elapsed() < timeout // exit
             } {}
         }
 
-        Ok(timer.elapsed().as_millis() < 10_000)
+        Ok(timer.elapsed() < timeout)
+    }
+
+    /// Requests a new state for an axis without waiting for the transition to happen.
+    ///
+    /// States which do not exist on the firmware of the ODrive fail with
+    /// `ODriveError::Unsupported`, see `AxisState::is_supported_by`. In particular,
+    /// `AxisState::SensorlessControl` does not exist on firmware 0.5 and later, which run sensorless
+    /// control as closed loop control with `<axis>.config.enable_sensorless_mode` set.
+    pub fn request_state(&mut self, axis: AxisID, requested_state: AxisState) -> ODriveResult<()> {
+        self.check_state_supported(requested_state)?;
+        self.set_axis_property(axis, "requested_state", requested_state as u8)
+    }
+
+    /// Requests a new state for an axis and blocks until the transition has finished.
+    /// See `wait_for_state` for how the outcome is determined.
+    pub fn change_state(&mut self, axis: AxisID, requested_state: AxisState) -> ODriveResult<StateOutcome> {
+        let timeout = self.timeouts.state_change;
+        self.change_state_with_timeout(axis, requested_state, timeout)
+    }
+
+    /// Like `change_state`, but waits for at most `timeout` instead of `Timeouts::state_change`.
+    pub fn change_state_with_timeout(&mut self, axis: AxisID, requested_state: AxisState,
+                                     timeout: Duration) -> ODriveResult<StateOutcome> {
+        self.request_state(axis, requested_state)?;
+        self.wait_for_state_with_timeout(axis, requested_state, timeout)
+    }
+
+    /// Blocks until a previously requested state transition has finished.
+    ///
+    /// For terminal states (see `AxisState::is_terminal`), this waits until `<axis>.current_state`
+    /// equals the requested state. For states which run to completion, such as the calibration
+    /// states, this waits until the axis has returned to `Idle`.
+    /// In both cases, if the axis ends up in `Idle` with errors set, those errors are returned.
+    /// On firmware 0.6 and later, the procedure result is returned instead.
+    /// The timeout is `Timeouts::state_change`.
+    pub fn wait_for_state(&mut self, axis: AxisID, requested_state: AxisState) -> ODriveResult<StateOutcome> {
+        let timeout = self.timeouts.state_change;
+        self.wait_for_state_with_timeout(axis, requested_state, timeout)
+    }
+
+    /// Like `wait_for_state`, but waits for at most `timeout` instead of `Timeouts::state_change`.
+    pub fn wait_for_state_with_timeout(&mut self, axis: AxisID, requested_state: AxisState,
+                                       timeout: Duration) -> ODriveResult<StateOutcome> {
+        let timer = Instant::now();
+        loop {
+            let current_state = self.read_axis_state(axis, "current_state")?;
+            if current_state == requested_state as u8 && requested_state.is_terminal() {
+                return Ok(StateOutcome::Reached);
+            }
+
+            // The axis might not have picked up the request yet, in which case
+            // `requested_state` will still hold it.
+            if current_state == AxisState::Idle as u8
+                && self.read_axis_state(axis, "requested_state")? == AxisState::Undefined as u8 {
+                if paths::PROCEDURE_RESULT.resolve(self.firmware_version()?).is_some() {
+                    match self.read_procedure_result(axis)? {
+                        // The procedure result is updated after the state.
+                        ProcedureResult::Busy => {}
+                        ProcedureResult::Success if !requested_state.is_terminal() => return Ok(StateOutcome::Reached),
+                        result => return Ok(StateOutcome::Aborted(result)),
+                    }
+                    if timer.elapsed() >= timeout {
+                        return Ok(StateOutcome::TimedOut);
+                    }
+                    continue;
+                }
+
+                let errors = self.read_axis_error(axis)?;
+                return if errors.is_empty() && !requested_state.is_terminal() {
+                    Ok(StateOutcome::Reached)
+                } else {
+                    Ok(StateOutcome::ReturnedToIdle(errors))
+                };
+            }
+
+            if timer.elapsed() >= timeout {
+                return Ok(StateOutcome::TimedOut);
+            }
+        }
+    }
+
+    /// Only reads the firmware version for states which do not exist on every version.
+    fn check_state_supported(&mut self, state: AxisState) -> ODriveResult<()> {
+        if state.depends_on_firmware() {
+            let firmware = self.firmware_version()?;
+            if !state.is_supported_by(firmware) {
+                return Err(ODriveError::Unsupported(format!("the {:?} state", state), firmware));
+            }
+        }
+        Ok(())
+    }
+
+    fn read_axis_state(&mut self, axis: AxisID, property: &str) -> ODriveResult<u8> {
+        let response = self.get_axis_property(axis, property)?;
+        self.parse_response(response)
+    }
+}
+
+/// # Zeroing
+/// The position estimate of an axis can be shifted, for example to make the position found by
+/// homing read as zero. Positions are in encoder counts, or in turns on firmware 0.5 and later.
+impl<T> ODrive<T> where T: Read + Write {
+    /// Sets the linear count of the encoder of an axis, using the `es` command. This shifts the
+    /// position estimate without moving the motor, but leaves the setpoint where it was, so an axis
+    /// in closed loop control will move to make up the difference. See `set_current_position_as`.
+    ///
+    /// Firmware 0.6 and later have no encoder counts on the axis, so this fails with
+    /// `ODriveError::Unsupported` on those versions.
+    pub fn set_encoder_linear_count(&mut self, axis: AxisID, count: i32) -> ODriveResult<()> {
+        let firmware = self.firmware_version()?;
+        if firmware.sets_position_in_turns() {
+            return Err(ODriveError::Unsupported("encoder linear counts".to_owned(), firmware));
+        }
+        writeln!(self, "es {} {}", axis as u8, count).map_err(ODriveError::Io)?;
+        self.flush().map_err(ODriveError::Io)
+    }
+
+    /// Shifts the position estimate of an axis so that its current position reads as `position`.
+    /// If the axis is in closed loop control, the position setpoint is shifted by the same amount,
+    /// so that the axis does not move. The shift assumes a setpoint which is not changing, such as
+    /// after homing or while holding a position.
+    ///
+    /// Before firmware 0.6, the estimate is set through the linear count of the encoder, and so is
+    /// rounded to a whole count. The position which was set is returned.
+    pub fn set_current_position_as(&mut self, axis: AxisID, position: f32) -> ODriveResult<f32> {
+        let firmware = self.firmware_version()?;
+        let estimate_path = self.versioned_path(&paths::POSITION_ESTIMATE)?;
+        let closed_loop = self.read_axis_state(axis, "current_state")? == AxisState::ClosedLoopControl as u8;
+        let estimate: f32 = self.read_axis_property(axis, estimate_path)?;
+        let setpoint = if closed_loop {
+            let setpoint_path = self.versioned_path(&paths::POSITION_SETPOINT)?;
+            Some((setpoint_path, self.read_axis_property::<f32>(axis, setpoint_path)?))
+        } else {
+            None
+        };
+
+        let position = if firmware.sets_position_in_turns() {
+            writeln!(self, "es {} {}", axis as u8, position).map_err(ODriveError::Io)?;
+            self.flush().map_err(ODriveError::Io)?;
+            position
+        } else {
+            let counts_per_unit = if firmware.uses_turns() { f32::from(self.read_encoder_cpr(axis)?) } else { 1.0 };
+            let count = (position * counts_per_unit).round() as i32;
+            self.set_encoder_linear_count(axis, count)?;
+            count as f32 / counts_per_unit
+        };
+
+        if let Some((setpoint_path, setpoint)) = setpoint {
+            self.set_axis_property(axis, setpoint_path, setpoint + position - estimate)?;
+        }
+        Ok(position)
+    }
+}
+
+/// # Calibration
+/// Each calibration runs as a state of the axis, and blocks until the axis has returned to `Idle`
+/// or `Timeouts::state_change` has passed. The result tells whether the calibration succeeded, and
+/// `ODriveError::StateTimedOut` is returned if it was still running at the timeout.
+///
+/// Firmware before 0.6 has no procedure results. On those versions, a calibration which fails
+/// returns `ODriveError::AxisFailed` with the errors set on `<axis>.error`, and `read_all_errors`
+/// gives the details.
+impl<T> ODrive<T> where T: Read + Write {
+    /// Measures the phase resistance and inductance of the motor.
+    pub fn calibrate_motor(&mut self, axis: AxisID) -> ODriveResult<ProcedureResult> {
+        self.run_calibration(axis, AxisState::MotorCalibration)
+    }
+
+    /// Measures the offset between the encoder position and the electrical phase.
+    pub fn calibrate_encoder_offset(&mut self, axis: AxisID) -> ODriveResult<ProcedureResult> {
+        self.run_calibration(axis, AxisState::EncoderOffsetCalibration)
+    }
+
+    /// Runs motor calibration, followed by encoder offset calibration or an index search.
+    pub fn run_full_calibration(&mut self, axis: AxisID) -> ODriveResult<ProcedureResult> {
+        self.run_calibration(axis, AxisState::FullCalibrationSequence)
+    }
+
+    fn run_calibration(&mut self, axis: AxisID, state: AxisState) -> ODriveResult<ProcedureResult> {
+        match self.change_state(axis, state)? {
+            StateOutcome::Reached => Ok(ProcedureResult::Success),
+            StateOutcome::Aborted(result) => Ok(result),
+            StateOutcome::TimedOut => Err(ODriveError::StateTimedOut(axis, state)),
+            StateOutcome::ReturnedToIdle(errors) => Err(ODriveError::AxisFailed(axis, errors)),
+        }
+    }
+}
+
+/// # Firmware
+/// Property names and units changed between firmware versions. Commands which depend on the
+/// version read it from the ODrive the first time they are used, unless the connection was made
+/// with `connect`, which reads it up front.
+///
+/// Firmware 0.4, 0.5 and 0.6 are supported. The ODrive S1 and ODrive Pro, which run 0.6, have a
+/// single axis, `AxisID::Zero`.
+impl<T> ODrive<T> where T: Read + Write {
+    /// Creates a connection and reads the firmware version of the ODrive.
+    pub fn connect(io_stream: T) -> ODriveResult<Self> {
+        let mut odrive = Self::new(io_stream);
+        odrive.firmware_version()?;
+        Ok(odrive)
+    }
+
+    /// Returns the firmware version of the connected ODrive.
+    ///
+    /// The version is read from the device the first time this is called, and cached afterwards.
+    pub fn firmware_version(&mut self) -> ODriveResult<FirmwareVersion> {
+        if let Some(version) = self.firmware {
+            return Ok(version);
+        }
+
+        let version = FirmwareVersion::new(
+            self.read_version_component("fw_version_major")?,
+            self.read_version_component("fw_version_minor")?,
+            self.read_version_component("fw_version_revision")?,
+        );
+        self.firmware = Some(version);
+        Ok(version)
+    }
+
+    fn read_version_component(&mut self, property: &str) -> ODriveResult<u8> {
+        let response = self.get_config_property(property)?;
+        self.parse_response(response)
+    }
+
+    /// The axes of the connected ODrive. The S1 and Pro, which run firmware 0.6, have one axis.
+    pub(crate) fn axes(&mut self) -> ODriveResult<&'static [AxisID]> {
+        if self.firmware_version()? >= FirmwareVersion::new(0, 6, 0) {
+            Ok(&[AxisID::Zero])
+        } else {
+            Ok(&[AxisID::Zero, AxisID::One])
+        }
+    }
+
+    /// Resolves the path of a property for the firmware of the connected ODrive.
+    fn versioned_path(&mut self, path: &VersionedPath) -> ODriveResult<&'static str> {
+        let firmware = self.firmware_version()?;
+        path.resolve(firmware).ok_or_else(|| ODriveError::Unsupported(path.feature.to_owned(), firmware))
+    }
+}
+
+/// # Reconnecting
+/// After a reboot, the ODrive enumerates again on USB and the old connection no longer works.
+/// `reconnect` closes it and opens a new one through a function supplied by the caller, which
+/// typically opens the serial port by name.
+impl<T> ODrive<T> where T: Read + Write {
+    /// Closes this connection and opens a new one with `opener`, keeping the timeouts and
+    /// synchronization options.
+    ///
+    /// The new connection is only returned once the firmware version could be read through it,
+    /// which may differ from the version before. Until then, `opener` is called again every 50 ms
+    /// until `Timeouts::reconnect` has passed, after which the last error is returned.
+    pub fn reconnect<F>(self, mut opener: F) -> ODriveResult<Self> where F: FnMut() -> io::Result<T> {
+        let timeouts = self.timeouts;
+        let sync = self.sync;
+        // Release the old stream first, as the device may not be opened twice.
+        drop(self);
+
+        let timer = Instant::now();
+        loop {
+            let error = match opener() {
+                Ok(io_stream) => {
+                    let mut odrive = Self::with_timeouts(io_stream, timeouts);
+                    odrive.sync = sync;
+                    match odrive.firmware_version() {
+                        Ok(_) => return Ok(odrive),
+                        Err(error) => error,
+                    }
+                }
+                Err(error) => ODriveError::Io(error),
+            };
+            if timer.elapsed() >= timeouts.reconnect {
+                return Err(error);
+            }
+            sleep(RECONNECT_INTERVAL);
+        }
+    }
+
+    /// Reboots the ODrive and reconnects to it with `opener`. See `reboot` and `reconnect`.
+    pub fn reboot_and_reconnect<F>(mut self, opener: F) -> ODriveResult<Self> where F: FnMut() -> io::Result<T> {
+        self.reboot()?;
+        self.reconnect(opener)
+    }
+}
+
+/// # Synchronization
+impl<T> ODrive<T> where T: Read + Write {
+    /// Discards any input left over from earlier requests, such as a response which arrived after
+    /// its request timed out.
+    ///
+    /// If `SyncOptions::sentinel` is set, this then reads `vbus_voltage` with a checksum and
+    /// discards everything received before the checksummed response, which cannot be mistaken for
+    /// a stale response as no other request is checksummed. `ODriveError::Desynchronized` is
+    /// returned if the response never arrives.
+    ///
+    /// This happens automatically before a request when the connection may be out of sync. It is
+    /// only necessary to call this directly when using the `Write` implementation.
+    pub fn resync(&mut self) -> ODriveResult<()> {
+        self.drain_input()?;
+
+        if self.sync.sentinel {
+            writeln!(self, "{}*{}", SENTINEL_REQUEST, checksum(SENTINEL_REQUEST.as_bytes())).map_err(ODriveError::Io)?;
+            self.flush().map_err(ODriveError::Io)?;
+            let timer = Instant::now();
+            loop {
+                match self.read_line() {
+                    Ok(ref line) if has_valid_checksum(line) => break,
+                    Ok(_) | Err(ODriveError::Desynchronized(_)) if timer.elapsed() < self.timeouts.read => {}
+                    Ok(_) | Err(ODriveError::Desynchronized(_)) | Err(ODriveError::NoMessageReceived(_)) => {
+                        return Err(ODriveError::Desynchronized(
+                            "no response to the synchronization request".to_owned()));
+                    }
+                    Err(error) => return Err(error),
+                }
+            }
+        }
+
+        self.stale = false;
+        Ok(())
+    }
+
+    /// Called before sending a request which expects a response.
+    fn begin_request(&mut self) -> ODriveResult<()> {
+        if self.stale || self.sync.drain_before_request {
+            self.resync()
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// # Properties
+/// Any property of the ODrive can be read and written by its path, for properties which do not
+/// have a dedicated method. Paths are written the same way as in `odrivetool`, such as
+/// `vbus_voltage` or `axis0.motor.config.current_lim`.
+///
+/// Values are converted with `PropertyValue`, which sends and reads booleans as `1` and `0`.
+impl<T> ODrive<T> where T: Read + Write {
+    /// Reads a property and parses it into `V`. If the response cannot be parsed,
+    /// `ODriveError::InvalidMessageReceived` is returned.
+    pub fn read_property<V: PropertyValue>(&mut self, path: &str) -> ODriveResult<V> {
+        let response = self.get_config_property(path)?;
+        self.parse_response(response)
+    }
+
+    /// Writes a value to a property.
+    pub fn write_property<V: PropertyValue>(&mut self, path: &str, value: V) -> ODriveResult<()> {
+        self.set_config_property(path, value)
+    }
+
+    /// Reads a property relative to an axis, such as `motor.config.current_lim`.
+    pub fn read_axis_property<V: PropertyValue>(&mut self, axis: AxisID, path: &str) -> ODriveResult<V> {
+        let response = self.get_axis_property(axis, path)?;
+        self.parse_response(response)
+    }
+
+    /// Writes a value to a property relative to an axis, such as `motor.config.current_lim`.
+    pub fn write_axis_property<V: PropertyValue>(&mut self, axis: AxisID, path: &str, value: V) -> ODriveResult<()> {
+        self.set_axis_property(axis, path, value)
+    }
+}
+
+/// # Transactions
+/// A sequence of configuration changes can be run as a transaction, so that the ODrive is not left
+/// half configured if one of them fails. Every configuration property, which is a property below a
+/// `config` object, records its value before it is first written. If the transaction fails, the
+/// recorded values are written back in reverse order.
+///
+/// Other writes, such as setpoints and state requests, are not undone. Saving the configuration
+/// inside a transaction saves it as it is at that point.
+impl<T> ODrive<T> where T: Read + Write {
+    /// Runs `changes` as a transaction. If it returns an error, the configuration properties it
+    /// wrote are restored, and the error is returned. Return `ODriveError::TransactionAborted` from
+    /// `changes` to abort the transaction explicitly.
+    ///
+    /// If some properties cannot be restored, `ODriveError::RollbackFailed` is returned with the
+    /// original error. A transaction started inside another one is part of the outer transaction:
+    /// if the inner one fails, only the properties it wrote are restored, to their values when it
+    /// started, and the outer transaction restores the rest if it fails as well.
+    pub fn transaction<F, R>(&mut self, changes: F) -> ODriveResult<R> where F: FnOnce(&mut Self) -> ODriveResult<R> {
+        let outer_start = self.journal.as_ref().map(|_| self.journal_start);
+        let start = self.journal.get_or_insert_with(Vec::new).len();
+        self.journal_start = start;
+        // Ends the transaction even if `changes` panics.
+        let guard = TransactionGuard { odrive: self, outer_start };
+
+        let error = match changes(guard.odrive) {
+            Ok(value) => return Ok(value),
+            Err(error) => error,
+        };
+
+        let undo = guard.odrive.journal.as_mut().map(|journal| journal.split_off(start)).unwrap_or_default();
+        let mut failed = Vec::new();
+        for (path, previous) in undo.into_iter().rev() {
+            if guard.odrive.write_raw_property(&path, &previous).is_err() {
+                failed.push(path);
+            }
+        }
+        if failed.is_empty() {
+            Err(error)
+        } else {
+            Err(ODriveError::RollbackFailed(Box::new(error), failed))
+        }
+    }
+
+    /// Records the value of a configuration property before it is first written in a transaction.
+    fn record_previous_value(&mut self, path: &str) -> ODriveResult<()> {
+        let recorded = match &self.journal {
+            Some(journal) => journal[self.journal_start..].iter().any(|(recorded, _)| recorded == path),
+            None => return Ok(()),
+        };
+        if recorded || !path.split('.').any(|segment| segment == "config") {
+            return Ok(());
+        }
+
+        let previous = self.get_config_property(path)?;
+        if let Some(journal) = &mut self.journal {
+            journal.push((path.to_owned(), previous));
+        }
+        Ok(())
+    }
+}
+
+/// Ends a transaction when it goes out of scope, restoring the journal to the state of the
+/// enclosing transaction, or removing it if there is none.
+struct TransactionGuard<'a, T> where T: Read {
+    odrive: &'a mut ODrive<T>,
+    outer_start: Option<usize>,
+}
+
+impl<T> Drop for TransactionGuard<'_, T> where T: Read {
+    fn drop(&mut self) {
+        match self.outer_start {
+            Some(start) => self.odrive.journal_start = start,
+            None => {
+                self.odrive.journal = None;
+                self.odrive.journal_start = 0;
+            }
+        }
     }
 }
 
 // Implement private helper methods
 impl<T> ODrive<T> where T: Read + Write {
-    fn set_config_property<D: Display>(&mut self, param: &str, value: D) -> ODriveResult<()> {
+    pub(crate) fn set_config_property<V: PropertyValue>(&mut self, param: &str, value: V) -> ODriveResult<()> {
+        self.record_previous_value(param)?;
+        self.write_raw_property(param, &value.format())
+    }
+
+    /// Writes a value which is already formatted for the ASCII protocol.
+    fn write_raw_property(&mut self, param: &str, value: &str) -> ODriveResult<()> {
         writeln!(self, "w {} {}", param, value).map_err(ODriveError::Io)?;
+        if self.sync.resync_after_write {
+            self.stale = true;
+        }
         self.flush().map_err(ODriveError::Io)
     }
 
-    fn get_config_property(&mut self, param: &str) -> ODriveResult<String> {
+    pub(crate) fn get_config_property(&mut self, param: &str) -> ODriveResult<String> {
+        self.begin_request()?;
         writeln!(self, "r {}", param).map_err(ODriveError::Io)?;
         self.flush().map_err(ODriveError::Io)?;
         self.read_odrive_response()
     }
 
-    fn set_axis_property<D: Display>(&mut self, axis: AxisID, property: &str, value: D) -> ODriveResult<()> {
+    fn set_axis_property<V: PropertyValue>(&mut self, axis: AxisID, property: &str, value: V) -> ODriveResult<()> {
         let config = format!("axis{}.{}", axis as u8, property);
         self.set_config_property(&config, value)
     }
@@ -232,7 +1012,16 @@ impl<T> ODrive<T> where T: Read + Write {
         self.get_config_property(&config)
     }
 
-    fn set_axis_config_property<D: Display>(&mut self, axis: AxisID, name: &str, value: D) -> ODriveResult<()> {
+    /// Reads a property holding an enumeration with the raw representation `R`.
+    fn read_axis_enum<R: PropertyValue, E: TryFrom<R>>(&mut self, axis: AxisID, property: &str) -> ODriveResult<E> {
+        let response = self.get_axis_property(axis, property)?;
+        match R::parse(&response).and_then(|value| E::try_from(value).ok()) {
+            Some(value) => Ok(value),
+            None => Err(self.invalid_message(response)),
+        }
+    }
+
+    fn set_axis_config_property<V: PropertyValue>(&mut self, axis: AxisID, name: &str, value: V) -> ODriveResult<()> {
         let config = format!("axis{}.config.{}", axis as u8, name);
         self.set_config_property(&config, value)
     }
@@ -243,10 +1032,49 @@ impl<T> ODrive<T> where T: Read + Write {
     }
 }
 
+/// The startup procedures of an axis, as stored in `<axis>.config.startup_*`.
+///
+/// The ODrive runs the enabled procedures in the order of the fields below.
+#[derive(Debug, Default, Eq, PartialEq, Hash, Copy, Clone)]
+pub struct StartupConfig {
+    pub motor_calibration: bool,
+    pub encoder_index_search: bool,
+    pub encoder_offset_calibration: bool,
+    /// Only available on firmware 0.5 and later. Always `false` when read from older firmware.
+    pub homing: bool,
+    pub closed_loop_control: bool,
+    pub sensorless_control: bool,
+}
+
+impl StartupConfig {
+    /// Checks that every enabled procedure can run given the procedures before it.
+    /// `motor_pre_calibrated` and `encoder_pre_calibrated` are the values of
+    /// `<axis>.motor.config.pre_calibrated` and `<axis>.encoder.config.pre_calibrated`.
+    pub fn validate(&self, motor_pre_calibrated: bool, encoder_pre_calibrated: bool) -> ODriveResult<()> {
+        let motor_ready = self.motor_calibration || motor_pre_calibrated;
+        let encoder_ready = self.encoder_offset_calibration || encoder_pre_calibrated;
+        let spins_motor = self.encoder_index_search || self.encoder_offset_calibration || self.homing
+            || self.closed_loop_control || self.sensorless_control;
+
+        if self.closed_loop_control && self.sensorless_control {
+            Err(ODriveError::InvalidConfiguration(
+                "closed loop control and sensorless control cannot both run at startup".to_owned()))
+        } else if spins_motor && !motor_ready {
+            Err(ODriveError::InvalidConfiguration(
+                "startup procedures require motor calibration or a pre-calibrated motor".to_owned()))
+        } else if (self.homing || self.closed_loop_control) && !encoder_ready {
+            Err(ODriveError::InvalidConfiguration(
+                "closed loop control and homing require encoder offset calibration or a pre-calibrated encoder".to_owned()))
+        } else {
+            Ok(())
+        }
+    }
+}
+
 /// # Startup Configuration
 /// The ODrive motor controllers have several optional startup procedures which can be enabled.
 /// Each of them has an associated getter and setter which can be invoked to read to and write from
-/// their value.
+/// their value, and all of them can be read and written at once through `StartupConfig`.
 ///
 /// From the official documentation:
 /// > By default the ODrive takes no action at startup and goes to idle immediately.
@@ -259,6 +1087,8 @@ impl<T> ODrive<T> where T: Read + Write {
 /// > 4. `<axis>.config.startup_closed_loop_control`
 /// > 5. `<axis>.config.startup_sensorless_control`
 ///
+/// Firmware 0.5 adds `<axis>.config.startup_homing`, which runs before closed loop control.
+///
 /// For further information, see the documentation for `AxisState`.
 impl<T> ODrive<T> where T: Read + Write {
     pub fn set_startup_motor_calibration(&mut self, axis: AxisID, value: bool) -> ODriveResult<()> {
@@ -273,85 +1103,165 @@ impl<T> ODrive<T> where T: Read + Write {
         self.set_axis_config_property(axis, "startup_encoder_offset_calibration", value as u8)
     }
 
+    /// Only available on firmware 0.5 and later.
+    pub fn set_startup_homing(&mut self, axis: AxisID, value: bool) -> ODriveResult<()> {
+        let path = self.versioned_path(&paths::STARTUP_HOMING)?;
+        self.set_axis_property(axis, path, value as u8)
+    }
+
     pub fn set_startup_closed_loop_control(&mut self, axis: AxisID, value: bool) -> ODriveResult<()> {
         self.set_axis_config_property(axis, "startup_closed_loop_control", value as u8)
     }
 
+    /// Only available on firmware 0.4.
     pub fn set_startup_sensorless_control(&mut self, axis: AxisID, value: bool) -> ODriveResult<()> {
-        self.set_axis_config_property(axis, "startup_sensorless_control", value as u8)
+        let path = self.versioned_path(&paths::STARTUP_SENSORLESS_CONTROL)?;
+        self.set_axis_property(axis, path, value as u8)
     }
 
     pub fn read_startup_motor_calibration(&mut self, axis: AxisID) -> ODriveResult<bool> {
-        let response = self.get_axis_config_property(axis, "startup_motor_calibration")?;
-        match response.parse::<u8>() {
-            Ok(val) => match val {
-                0 => Ok(false),
-                1 => Ok(true),
-                _ => Err(ODriveError::InvalidMessageReceived(response))
-            },
-            Err(_error) => {
-                Err(ODriveError::InvalidMessageReceived(response))
-            }
-        }
+        self.read_startup_flag(axis, "startup_motor_calibration")
     }
 
-    pub fn read_startup_encoder_index_search(&mut self, axis: AxisID, value: bool) -> ODriveResult<bool> {
-        let response = self.get_axis_config_property(axis, "startup_encoder_index_search")?;
-        match response.parse::<u8>() {
-            Ok(val) => match val {
-                0 => Ok(false),
-                1 => Ok(true),
-                _ => Err(ODriveError::InvalidMessageReceived(response))
-            },
-            Err(_error) => {
-                Err(ODriveError::InvalidMessageReceived(response))
-            }
-        }
+    pub fn read_startup_encoder_index_search(&mut self, axis: AxisID) -> ODriveResult<bool> {
+        self.read_startup_flag(axis, "startup_encoder_index_search")
+    }
+
+    pub fn read_startup_encoder_offset_calibration(&mut self, axis: AxisID) -> ODriveResult<bool> {
+        self.read_startup_flag(axis, "startup_encoder_offset_calibration")
     }
 
-    pub fn read_startup_encoder_offset_calibration(&mut self, axis: AxisID, value: bool) -> ODriveResult<bool> {
-        let response = self.get_axis_config_property(axis, "startup_encoder_offset_calibration")?;
-        match response.parse::<u8>() {
-            Ok(val) => match val {
-                0 => Ok(false),
-                1 => Ok(true),
-                _ => Err(ODriveError::InvalidMessageReceived(response))
-            },
-            Err(_error) => {
-                Err(ODriveError::InvalidMessageReceived(response))
+    /// Only available on firmware 0.5 and later.
+    pub fn read_startup_homing(&mut self, axis: AxisID) -> ODriveResult<bool> {
+        let path = self.versioned_path(&paths::STARTUP_HOMING)?;
+        self.read_axis_property(axis, path)
+    }
+
+    pub fn read_startup_closed_loop_control(&mut self, axis: AxisID) -> ODriveResult<bool> {
+        self.read_startup_flag(axis, "startup_closed_loop_control")
+    }
+
+    /// Only available on firmware 0.4.
+    pub fn read_startup_sensorless_control(&mut self, axis: AxisID) -> ODriveResult<bool> {
+        let path = self.versioned_path(&paths::STARTUP_SENSORLESS_CONTROL)?;
+        self.read_axis_property(axis, path)
+    }
+
+    /// Reads every startup procedure flag of an axis.
+    /// Procedures which the firmware does not have are read as `false`.
+    pub fn read_startup_config(&mut self, axis: AxisID) -> ODriveResult<StartupConfig> {
+        Ok(StartupConfig {
+            motor_calibration: self.read_startup_motor_calibration(axis)?,
+            encoder_index_search: self.read_startup_encoder_index_search(axis)?,
+            encoder_offset_calibration: self.read_startup_encoder_offset_calibration(axis)?,
+            homing: self.read_optional_startup_flag(axis, &paths::STARTUP_HOMING)?,
+            closed_loop_control: self.read_startup_closed_loop_control(axis)?,
+            sensorless_control: self.read_optional_startup_flag(axis, &paths::STARTUP_SENSORLESS_CONTROL)?,
+        })
+    }
+
+    /// Validates a startup configuration against the calibration state of the axis, then writes
+    /// the flags which differ from the current configuration.
+    ///
+    /// `ODriveError::InvalidConfiguration` is returned without writing anything if the
+    /// configuration could not run, such as closed loop control on a motor which is neither
+    /// calibrated at startup nor pre-calibrated. Enabling a procedure which the firmware does not
+    /// have fails with `ODriveError::Unsupported`, also without writing anything.
+    ///
+    /// Firmware 0.6 has no `pre_calibrated` flags, as it keeps calibration results whenever the
+    /// configuration is saved. The motor and encoder are assumed to be calibrated on those versions.
+    pub fn write_startup_config(&mut self, axis: AxisID, config: &StartupConfig) -> ODriveResult<()> {
+        let motor_pre_calibrated = self.read_optional_pre_calibrated(axis, &paths::MOTOR_PRE_CALIBRATED)?;
+        let encoder_pre_calibrated = self.read_optional_pre_calibrated(axis, &paths::ENCODER_PRE_CALIBRATED)?;
+        config.validate(motor_pre_calibrated, encoder_pre_calibrated)?;
+
+        let current = self.read_startup_config(axis)?;
+        let flags = vec![
+            (Ok("config.startup_motor_calibration"), current.motor_calibration, config.motor_calibration),
+            (Ok("config.startup_encoder_index_search"), current.encoder_index_search, config.encoder_index_search),
+            (Ok("config.startup_encoder_offset_calibration"), current.encoder_offset_calibration,
+             config.encoder_offset_calibration),
+            (self.versioned_path(&paths::STARTUP_HOMING), current.homing, config.homing),
+            (Ok("config.startup_closed_loop_control"), current.closed_loop_control, config.closed_loop_control),
+            (self.versioned_path(&paths::STARTUP_SENSORLESS_CONTROL), current.sensorless_control,
+             config.sensorless_control),
+        ];
+        // Resolve every path which is written first, so an unsupported flag leaves nothing written.
+        let mut changes = Vec::new();
+        for (path, current, requested) in flags {
+            if current != requested {
+                changes.push((path?, requested));
             }
         }
+        for (path, requested) in changes {
+            self.set_axis_property(axis, path, requested as u8)?;
+        }
+        Ok(())
     }
 
-    pub fn read_startup_closed_loop_control(&mut self, axis: AxisID, value: bool) -> ODriveResult<bool> {
-        let response = self.get_axis_config_property(axis, "startup_closed_loop_control")?;
-        match response.parse::<u8>() {
-            Ok(val) => match val {
-                0 => Ok(false),
-                1 => Ok(true),
-                _ => Err(ODriveError::InvalidMessageReceived(response))
-            },
-            Err(_error) => {
-                Err(ODriveError::InvalidMessageReceived(response))
-            }
+    fn read_startup_flag(&mut self, axis: AxisID, name: &str) -> ODriveResult<bool> {
+        let response = self.get_axis_config_property(axis, name)?;
+        self.parse_response(response)
+    }
+
+    fn read_optional_startup_flag(&mut self, axis: AxisID, path: &VersionedPath) -> ODriveResult<bool> {
+        match self.versioned_path(path) {
+            Ok(path) => self.read_axis_property(axis, path),
+            Err(ODriveError::Unsupported(..)) => Ok(false),
+            Err(error) => Err(error),
         }
     }
 
-    pub fn read_startup_sensorless_control(&mut self, axis: AxisID, value: bool) -> ODriveResult<bool> {
-        let response = self.get_axis_config_property(axis, "startup_sensorless_control")?;
-        match response.parse::<u8>() {
-            Ok(val) => match val {
-                0 => Ok(false),
-                1 => Ok(true),
-                _ => Err(ODriveError::InvalidMessageReceived(response))
-            },
-            Err(_error) => {
-                Err(ODriveError::InvalidMessageReceived(response))
-            }
+    fn read_optional_pre_calibrated(&mut self, axis: AxisID, path: &VersionedPath) -> ODriveResult<bool> {
+        match self.versioned_path(path) {
+            Ok(path) => self.read_axis_property(axis, path),
+            Err(ODriveError::Unsupported(..)) => Ok(true),
+            Err(error) => Err(error),
         }
     }
 }
 
+/// Options for `ODrive::save_configuration_checked`.
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
+pub struct SaveOptions {
+    /// Request `Idle` on axes which are not idle, rather than failing. Defaults to `false`.
+    pub idle_axes: bool,
+    /// Paths of properties which must read the same after saving as before, such as
+    /// `axis0.motor.config.current_lim`.
+    pub verify: Vec<String>,
+}
+
+/// The error returned by `ODrive::save_configuration_checked`, with the connection to keep using.
+///
+/// Converts into the contained `ODriveError`, so `?` can be used in functions returning
+/// `ODriveResult`.
+#[derive(Debug)]
+pub struct SaveError<T> where T: Read {
+    /// What went wrong.
+    pub error: ODriveError,
+    /// The original connection if the configuration was not saved, the reopened connection if it
+    /// was, and `None` if reopening it failed.
+    pub odrive: Option<Box<ODrive<T>>>,
+}
+
+impl<T> fmt::Display for SaveError<T> where T: Read {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl<T> std::error::Error for SaveError<T> where T: Read + fmt::Debug {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl<T> From<SaveError<T>> for ODriveError where T: Read {
+    fn from(error: SaveError<T>) -> Self {
+        error.error
+    }
+}
+
 /// Configuration management.
 impl<T> ODrive<T> where T: Read + Write {
     /// Saves the current configuration of properties to the ODrives non-volatile memory, allowing
@@ -366,47 +1276,272 @@ impl<T> ODrive<T> where T: Read + Write {
         writeln!(self, "se").map_err(ODriveError::Io)?;
         self.flush().map_err(ODriveError::Io)
     }
+
+    /// Saves the configuration like `save_configuration`, and checks that it was saved.
+    ///
+    /// The ODrive refuses to save while an axis is not `Idle`, so this fails with
+    /// `ODriveError::InvalidConfiguration` before saving if one is not, unless
+    /// `SaveOptions::idle_axes` is set. Firmware 0.5 and later reboot after saving, and firmware
+    /// 0.4 is rebooted with `sr`, so that the properties in `SaveOptions::verify` are read back from
+    /// non-volatile memory rather than RAM. The connection is then reopened with `opener`, see
+    /// `reconnect`, and `ODriveError::NotPersisted` lists the properties which changed.
+    ///
+    /// On failure, the returned `SaveError` holds the connection to keep using, if there is one.
+    pub fn save_configuration_checked<F>(mut self, options: &SaveOptions, opener: F) -> Result<Self, SaveError<T>>
+        where F: FnMut() -> io::Result<T> {
+        let expected = match self.save_and_reboot(options) {
+            Ok(expected) => expected,
+            Err(error) => return Err(SaveError { error, odrive: Some(Box::new(self)) }),
+        };
+        let mut odrive = self.reconnect(opener).map_err(|error| SaveError { error, odrive: None })?;
+
+        let mut changed = Vec::new();
+        for (path, expected) in options.verify.iter().zip(expected) {
+            match odrive.get_config_property(path) {
+                Ok(value) if value == expected => {}
+                Ok(_) => changed.push(path.clone()),
+                Err(error) => return Err(SaveError { error, odrive: Some(Box::new(odrive)) }),
+            }
+        }
+        if changed.is_empty() {
+            Ok(odrive)
+        } else {
+            Err(SaveError { error: ODriveError::NotPersisted(changed), odrive: Some(Box::new(odrive)) })
+        }
+    }
+
+    /// Idles the axes if allowed, reads the properties to verify, then saves and makes sure the
+    /// ODrive reboots. Returns the values read.
+    fn save_and_reboot(&mut self, options: &SaveOptions) -> ODriveResult<Vec<String>> {
+        for &axis in self.axes()? {
+            if self.read_axis_state(axis, "current_state")? == AxisState::Idle as u8 {
+                continue;
+            }
+            if !options.idle_axes {
+                return Err(ODriveError::InvalidConfiguration(
+                    format!("axis {} must be idle to save the configuration", axis as u8)));
+            }
+            if self.change_state(axis, AxisState::Idle)? != StateOutcome::Reached {
+                return Err(ODriveError::InvalidConfiguration(
+                    format!("axis {} did not become idle to save the configuration", axis as u8)));
+            }
+        }
+
+        let mut expected = Vec::with_capacity(options.verify.len());
+        for path in &options.verify {
+            expected.push(self.get_config_property(path)?);
+        }
+
+        let reboots = self.firmware_version()?.reboots_on_save();
+        self.save_configuration()?;
+        if !reboots {
+            self.reboot()?;
+        }
+        Ok(expected)
+    }
+
+    /// Reboots the ODrive, using the `sr` command.
+    ///
+    /// A USB connection is closed by the reboot, and has to be reopened with `reconnect`.
+    pub fn reboot(&mut self) -> ODriveResult<()> {
+        writeln!(self, "sr").map_err(ODriveError::Io)?;
+        self.flush().map_err(ODriveError::Io)?;
+        // The firmware may have been updated before the reboot.
+        self.firmware = None;
+        self.stale = true;
+        Ok(())
+    }
+}
+
+/// # Watchdog
+/// Each axis has a watchdog which disarms it when it has not been fed within
+/// `<axis>.config.watchdog_timeout` seconds, and sets `AxisError::ErrorWatchdogTimerExpired`.
+/// Keeping it enabled ensures the motors stop if the host crashes or the connection is lost.
+///
+/// On firmware 0.4, the watchdog is enabled by any timeout above zero, `set_watchdog_enabled`
+/// fails with `ODriveError::Unsupported` and `read_watchdog_enabled` checks the timeout.
+/// `WatchdogKeepAlive` feeds the watchdog from a background thread.
+impl<T> ODrive<T> where T: Read + Write {
+    /// Feeds the watchdog of an axis, using the `u` command.
+    pub fn feed_watchdog(&mut self, axis: AxisID) -> ODriveResult<()> {
+        writeln!(self, "u {}", axis as u8).map_err(ODriveError::Io)?;
+        self.flush().map_err(ODriveError::Io)
+    }
+
+    /// Enables or disables the watchdog of an axis, using `<axis>.config.enable_watchdog`.
+    pub fn set_watchdog_enabled(&mut self, axis: AxisID, value: bool) -> ODriveResult<()> {
+        let path = self.versioned_path(&paths::ENABLE_WATCHDOG)?;
+        self.set_axis_property(axis, path, value)
+    }
+
+    /// Sets the watchdog timeout, in seconds.
+    pub fn set_watchdog_timeout(&mut self, axis: AxisID, value: f32) -> ODriveResult<()> {
+        self.set_axis_config_property(axis, "watchdog_timeout", value)
+    }
+
+    /// Reads whether the watchdog of an axis is enabled. On firmware 0.4, which has no
+    /// `<axis>.config.enable_watchdog`, this is whether the timeout is above zero.
+    pub fn read_watchdog_enabled(&mut self, axis: AxisID) -> ODriveResult<bool> {
+        match paths::ENABLE_WATCHDOG.resolve(self.firmware_version()?) {
+            Some(path) => self.read_axis_property(axis, path),
+            None => Ok(self.read_watchdog_timeout(axis)? > 0.0),
+        }
+    }
+
+    /// Reads the watchdog timeout, in seconds.
+    pub fn read_watchdog_timeout(&mut self, axis: AxisID) -> ODriveResult<f32> {
+        self.read_axis_property(axis, "config.watchdog_timeout")
+    }
+}
+
+/// Board configuration
+impl<T> ODrive<T> where T: Read + Write {
+    /// Sets the DC bus voltage above which the ODrive disarms, in volts.
+    pub fn set_dc_bus_overvoltage_trip_level(&mut self, value: f32) -> ODriveResult<()> {
+        self.set_config_property("config.dc_bus_overvoltage_trip_level", value)
+    }
+
+    /// Sets the DC bus voltage below which the ODrive disarms, in volts.
+    pub fn set_dc_bus_undervoltage_trip_level(&mut self, value: f32) -> ODriveResult<()> {
+        self.set_config_property("config.dc_bus_undervoltage_trip_level", value)
+    }
+
+    pub fn read_dc_bus_overvoltage_trip_level(&mut self) -> ODriveResult<f32> {
+        self.read_property("config.dc_bus_overvoltage_trip_level")
+    }
+
+    pub fn read_dc_bus_undervoltage_trip_level(&mut self) -> ODriveResult<f32> {
+        self.read_property("config.dc_bus_undervoltage_trip_level")
+    }
 }
 
 /// Motor configuration
 impl<T> ODrive<T> where T: Read + Write {
     pub fn set_motor_pole_pairs(&mut self, axis: AxisID, value: u16) -> ODriveResult<()> {
-        self.set_axis_property(axis, "motor.config.pole_pairs", value)
+        let path = self.versioned_path(&paths::MOTOR_POLE_PAIRS)?;
+        self.set_axis_property(axis, path, value)
     }
 
     pub fn set_motor_resistance_calib_max_voltage(&mut self, axis: AxisID, value: f32) -> ODriveResult<()> {
-        self.set_axis_property(axis, "motor.config.resistance_calib_max_voltage", value)
+        let path = self.versioned_path(&paths::MOTOR_RESISTANCE_CALIB_MAX_VOLTAGE)?;
+        self.set_axis_property(axis, path, value)
     }
 
     pub fn set_motor_requested_current_range(&mut self, axis: AxisID, value: f32) -> ODriveResult<()> {
-        self.set_axis_property(axis, "motor.config.requested_current_range", value)
+        let path = self.versioned_path(&paths::MOTOR_REQUESTED_CURRENT_RANGE)?;
+        self.set_axis_property(axis, path, value)
     }
 
     pub fn set_motor_current_control_bandwidth(&mut self, axis: AxisID, value: f32) -> ODriveResult<()> {
-        self.set_axis_property(axis, "motor.config.current_control_bandwidth", value)
+        let path = self.versioned_path(&paths::MOTOR_CURRENT_CONTROL_BANDWIDTH)?;
+        self.set_axis_property(axis, path, value)
     }
 
     pub fn set_motor_pre_calibrated(&mut self, axis: AxisID, value: bool) -> ODriveResult<()> {
-        self.set_axis_property(axis, "motor.config.pre_calibrated", value as u8)
+        let path = self.versioned_path(&paths::MOTOR_PRE_CALIBRATED)?;
+        self.set_axis_property(axis, path, value as u8)
+    }
+
+    /// Sets the motor type. Types which the firmware does not have fail with
+    /// `ODriveError::Unsupported`.
+    pub fn set_motor_type(&mut self, axis: AxisID, value: MotorType) -> ODriveResult<()> {
+        let path = self.versioned_path(&paths::MOTOR_TYPE)?;
+        let firmware = self.firmware_version()?;
+        if !value.is_supported_by(firmware) {
+            return Err(ODriveError::Unsupported(format!("the {:?} motor type", value), firmware));
+        }
+        self.set_axis_property(axis, path, value as u8)
+    }
+
+    /// Sets the motor current limit, in amps.
+    pub fn set_motor_current_limit(&mut self, axis: AxisID, value: f32) -> ODriveResult<()> {
+        let path = self.versioned_path(&paths::CURRENT_LIMIT)?;
+        self.set_axis_property(axis, path, value)
+    }
+
+    pub fn read_motor_pole_pairs(&mut self, axis: AxisID) -> ODriveResult<u16> {
+        let path = self.versioned_path(&paths::MOTOR_POLE_PAIRS)?;
+        self.read_axis_property(axis, path)
+    }
+
+    pub fn read_motor_resistance_calib_max_voltage(&mut self, axis: AxisID) -> ODriveResult<f32> {
+        let path = self.versioned_path(&paths::MOTOR_RESISTANCE_CALIB_MAX_VOLTAGE)?;
+        self.read_axis_property(axis, path)
+    }
+
+    pub fn read_motor_requested_current_range(&mut self, axis: AxisID) -> ODriveResult<f32> {
+        let path = self.versioned_path(&paths::MOTOR_REQUESTED_CURRENT_RANGE)?;
+        self.read_axis_property(axis, path)
+    }
+
+    pub fn read_motor_current_control_bandwidth(&mut self, axis: AxisID) -> ODriveResult<f32> {
+        let path = self.versioned_path(&paths::MOTOR_CURRENT_CONTROL_BANDWIDTH)?;
+        self.read_axis_property(axis, path)
+    }
+
+    pub fn read_motor_pre_calibrated(&mut self, axis: AxisID) -> ODriveResult<bool> {
+        let path = self.versioned_path(&paths::MOTOR_PRE_CALIBRATED)?;
+        self.read_axis_property(axis, path)
+    }
+
+    pub fn read_motor_type(&mut self, axis: AxisID) -> ODriveResult<MotorType> {
+        let path = self.versioned_path(&paths::MOTOR_TYPE)?;
+        self.read_axis_enum::<u8, _>(axis, path)
+    }
+
+    /// Reads the motor current limit, in amps.
+    pub fn read_motor_current_limit(&mut self, axis: AxisID) -> ODriveResult<f32> {
+        let path = self.versioned_path(&paths::CURRENT_LIMIT)?;
+        self.read_axis_property(axis, path)
     }
 }
 
 /// Encoder configuration
 impl<T> ODrive<T> where T: Read + Write {
+    /// Sets the encoder mode. Modes which the firmware does not have fail with
+    /// `ODriveError::Unsupported`.
     pub fn set_encoder_mode(&mut self, axis: AxisID, value: EncoderMode) -> ODriveResult<()> {
-        self.set_axis_property(axis, "encoder.config.mode", value as u8)
+        let path = self.versioned_path(&paths::ENCODER_MODE)?;
+        let firmware = self.firmware_version()?;
+        if !value.is_supported_by(firmware) {
+            return Err(ODriveError::Unsupported(format!("the {:?} encoder mode", value), firmware));
+        }
+        self.set_axis_property(axis, path, value as u16)
     }
 
     pub fn set_encoder_cpr(&mut self, axis: AxisID, value: u16) -> ODriveResult<()> {
-        self.set_axis_property(axis, "encoder.config.cpr", value)
+        let path = self.versioned_path(&paths::ENCODER_CPR)?;
+        self.set_axis_property(axis, path, value)
     }
 
     pub fn set_encoder_bandwidth(&mut self, axis: AxisID, value: f32) -> ODriveResult<()> {
-        self.set_axis_property(axis, "encoder.config.bandwidth", value)
+        let path = self.versioned_path(&paths::ENCODER_BANDWIDTH)?;
+        self.set_axis_property(axis, path, value)
     }
 
     pub fn set_encoder_pre_calibrated(&mut self, axis: AxisID, value: bool) -> ODriveResult<()> {
-        self.set_axis_property(axis, "encoder.config.pre_calibrated", value as u8)
+        let path = self.versioned_path(&paths::ENCODER_PRE_CALIBRATED)?;
+        self.set_axis_property(axis, path, value as u8)
+    }
+
+    pub fn read_encoder_mode(&mut self, axis: AxisID) -> ODriveResult<EncoderMode> {
+        let path = self.versioned_path(&paths::ENCODER_MODE)?;
+        self.read_axis_enum::<u16, _>(axis, path)
+    }
+
+    pub fn read_encoder_cpr(&mut self, axis: AxisID) -> ODriveResult<u16> {
+        let path = self.versioned_path(&paths::ENCODER_CPR)?;
+        self.read_axis_property(axis, path)
+    }
+
+    pub fn read_encoder_bandwidth(&mut self, axis: AxisID) -> ODriveResult<f32> {
+        let path = self.versioned_path(&paths::ENCODER_BANDWIDTH)?;
+        self.read_axis_property(axis, path)
+    }
+
+    pub fn read_encoder_pre_calibrated(&mut self, axis: AxisID) -> ODriveResult<bool> {
+        let path = self.versioned_path(&paths::ENCODER_PRE_CALIBRATED)?;
+        self.read_axis_property(axis, path)
     }
 }
 
@@ -428,7 +1563,187 @@ impl<T> ODrive<T> where T: Read + Write {
         self.set_axis_property(axis, "controller.config.vel_limit", value)
     }
 
+    /// Sets the control mode of an axis.
+    ///
+    /// Firmware 0.5 replaced trajectory control with an input mode of position control, so on those
+    /// versions `ControlMode::TrajectoryControl` selects position control with the `TRAP_TRAJ`
+    /// input mode, and the other modes select the `PASSTHROUGH` input mode.
+    /// `ControlMode::CurrentControl` selects torque control on those versions.
     pub fn set_control_mode(&mut self, axis: AxisID, mode: ControlMode) -> ODriveResult<()> {
-        self.set_axis_property(axis, "controller.config.control_mode", mode as u8)
+        let input_mode_path = match paths::INPUT_MODE.resolve(self.firmware_version()?) {
+            Some(path) => path,
+            None => return self.set_axis_property(axis, "controller.config.control_mode", mode as u8),
+        };
+
+        let (mode, input_mode) = match mode {
+            ControlMode::TrajectoryControl => (ControlMode::PositionControl, InputMode::TrapTraj),
+            mode => (mode, InputMode::Passthrough),
+        };
+        self.set_axis_property(axis, "controller.config.control_mode", mode as u8)?;
+        self.set_axis_property(axis, input_mode_path, input_mode as u8)
+    }
+
+    pub fn read_position_gain(&mut self, axis: AxisID) -> ODriveResult<f32> {
+        self.read_axis_property(axis, "controller.config.pos_gain")
+    }
+
+    pub fn read_velocity_gain(&mut self, axis: AxisID) -> ODriveResult<f32> {
+        self.read_axis_property(axis, "controller.config.vel_gain")
     }
-}
\ No newline at end of file
+
+    pub fn read_velocity_integrator_gain(&mut self, axis: AxisID) -> ODriveResult<f32> {
+        self.read_axis_property(axis, "controller.config.vel_integrator_gain")
+    }
+
+    pub fn read_velocity_limit(&mut self, axis: AxisID) -> ODriveResult<f32> {
+        self.read_axis_property(axis, "controller.config.vel_limit")
+    }
+
+    /// Reads the control mode of an axis. See `set_control_mode` for how the modes are mapped on
+    /// firmware 0.5 and later.
+    pub fn read_control_mode(&mut self, axis: AxisID) -> ODriveResult<ControlMode> {
+        let input_mode_path = paths::INPUT_MODE.resolve(self.firmware_version()?);
+        let mode = self.read_axis_enum::<u8, _>(axis, "controller.config.control_mode")?;
+        let input_mode_path = match input_mode_path {
+            Some(path) => path,
+            None => return Ok(mode),
+        };
+
+        match mode {
+            ControlMode::TrajectoryControl => Err(self.invalid_message((mode as u8).to_string())),
+            ControlMode::PositionControl => {
+                let input_mode: InputMode = self.read_axis_enum::<u8, _>(axis, input_mode_path)?;
+                if input_mode == InputMode::TrapTraj {
+                    Ok(ControlMode::TrajectoryControl)
+                } else {
+                    Ok(mode)
+                }
+            }
+            mode => Ok(mode),
+        }
+    }
+}
+
+/// # Errors
+/// The ODrive reports errors through a register on the axis and on each of its motor, encoder and
+/// controller. Each register is an OR-ed bitmask, and is decoded here into an `ErrorSet`.
+///
+/// Firmware 0.6 replaced these registers with `<axis>.active_errors`, so reading them fails with
+/// `ODriveError::Unsupported` on those versions. `read_active_errors`, `read_disarm_reason` and
+/// `read_procedure_result` are only available on firmware 0.6 and later.
+impl<T> ODrive<T> where T: Read + Write {
+    /// Reads and decodes `<axis>.error`.
+    pub fn read_axis_error(&mut self, axis: AxisID) -> ODriveResult<AxisErrors> {
+        self.read_error_register(axis, &paths::AXIS_ERROR)
+    }
+
+    /// Reads and decodes `<axis>.motor.error`.
+    pub fn read_motor_error(&mut self, axis: AxisID) -> ODriveResult<MotorErrors> {
+        self.read_error_register(axis, &paths::MOTOR_ERROR)
+    }
+
+    /// Reads and decodes `<axis>.encoder.error`.
+    pub fn read_encoder_error(&mut self, axis: AxisID) -> ODriveResult<EncoderErrors> {
+        self.read_error_register(axis, &paths::ENCODER_ERROR)
+    }
+
+    /// Reads and decodes `<axis>.controller.error`.
+    pub fn read_controller_error(&mut self, axis: AxisID) -> ODriveResult<ControllerErrors> {
+        self.read_error_register(axis, &paths::CONTROLLER_ERROR)
+    }
+
+    /// Reads and decodes `<axis>.active_errors`, the errors which currently keep the axis from
+    /// running.
+    pub fn read_active_errors(&mut self, axis: AxisID) -> ODriveResult<ActiveErrors> {
+        self.read_error_register(axis, &paths::ACTIVE_ERRORS)
+    }
+
+    /// Reads and decodes `<axis>.disarm_reason`, the errors which caused the axis to disarm most
+    /// recently. Unlike `<axis>.active_errors`, these remain set after the cause has gone away.
+    pub fn read_disarm_reason(&mut self, axis: AxisID) -> ODriveResult<ActiveErrors> {
+        self.read_error_register(axis, &paths::DISARM_REASON)
+    }
+
+    /// Reads `<axis>.procedure_result`, the outcome of the most recent state of the axis.
+    pub fn read_procedure_result(&mut self, axis: AxisID) -> ODriveResult<ProcedureResult> {
+        let path = self.versioned_path(&paths::PROCEDURE_RESULT)?;
+        self.read_axis_enum::<u8, _>(axis, path)
+    }
+
+    /// Reads every error register of an axis: `<axis>.active_errors` and `<axis>.disarm_reason` on
+    /// firmware 0.6 and later, and the registers of the axis, motor, encoder and controller before.
+    pub fn read_all_errors(&mut self, axis: AxisID) -> ODriveResult<AxisErrorReport> {
+        if !self.has_error_registers()? {
+            return Ok(AxisErrorReport {
+                active: self.read_active_errors(axis)?,
+                disarm_reason: self.read_disarm_reason(axis)?,
+                ..AxisErrorReport::default()
+            });
+        }
+        Ok(AxisErrorReport {
+            axis: self.read_axis_error(axis)?,
+            motor: self.read_motor_error(axis)?,
+            encoder: self.read_encoder_error(axis)?,
+            controller: self.read_controller_error(axis)?,
+            ..AxisErrorReport::default()
+        })
+    }
+
+    /// Clears every error register of an axis, returning the errors which were set beforehand.
+    ///
+    /// On firmware 0.5.2 and later this uses the `sc` command, which clears the errors of the whole
+    /// board rather than just the given axis. On older firmware, `<axis>.error`, `<axis>.motor.error`,
+    /// `<axis>.encoder.error` and `<axis>.controller.error` are each written to zero.
+    pub fn clear_errors(&mut self, axis: AxisID) -> ODriveResult<AxisErrorReport> {
+        let report = self.read_all_errors(axis)?;
+        if self.firmware_version()?.supports_clear_errors_command() {
+            self.send_clear_errors_command()?;
+        } else {
+            self.zero_error_registers(axis)?;
+        }
+        Ok(report)
+    }
+
+    /// Clears the errors of every axis of the board, returning the errors which were set
+    /// beforehand, indexed by axis.
+    pub fn clear_all_errors(&mut self) -> ODriveResult<Vec<AxisErrorReport>> {
+        let axes = self.axes()?;
+        let mut reports = Vec::with_capacity(axes.len());
+        for &axis in axes {
+            reports.push(self.read_all_errors(axis)?);
+        }
+        if self.firmware_version()?.supports_clear_errors_command() {
+            self.send_clear_errors_command()?;
+        } else {
+            for &axis in axes {
+                self.zero_error_registers(axis)?;
+            }
+        }
+        Ok(reports)
+    }
+
+    fn has_error_registers(&mut self) -> ODriveResult<bool> {
+        Ok(paths::AXIS_ERROR.resolve(self.firmware_version()?).is_some())
+    }
+
+    fn send_clear_errors_command(&mut self) -> ODriveResult<()> {
+        writeln!(self, "sc").map_err(ODriveError::Io)?;
+        self.flush().map_err(ODriveError::Io)
+    }
+
+    fn zero_error_registers(&mut self, axis: AxisID) -> ODriveResult<()> {
+        for property in &["error", "motor.error", "encoder.error", "controller.error"] {
+            self.set_axis_property(axis, property, 0)?;
+        }
+        Ok(())
+    }
+
+    fn read_error_register<E: ErrorFlag>(&mut self, axis: AxisID, path: &VersionedPath) -> ODriveResult<ErrorSet<E>> {
+        let property = self.versioned_path(path)?;
+        let response = self.get_axis_property(axis, property)?;
+        match response.parse::<u64>() {
+            Ok(bits) => Ok(ErrorSet::from_bits(bits)),
+            Err(_error) => Err(self.invalid_message(response))
+        }
+    }
+}
@@ -0,0 +1,214 @@
+//! Properties whose path changed between firmware versions, relative to an axis.
+
+use crate::firmware::FirmwareVersion;
+
+/// The path of a property on each generation of the firmware. `None` marks a generation which
+/// does not have the property.
+pub(super) struct VersionedPath {
+    /// Describes the property in `ODriveError::Unsupported`.
+    pub feature: &'static str,
+    pub v0_4: Option<&'static str>,
+    pub v0_5: Option<&'static str>,
+    pub v0_6: Option<&'static str>,
+}
+
+impl VersionedPath {
+    pub fn resolve(&self, firmware: FirmwareVersion) -> Option<&'static str> {
+        if firmware >= FirmwareVersion::new(0, 6, 0) {
+            self.v0_6
+        } else if firmware >= FirmwareVersion::new(0, 5, 0) {
+            self.v0_5
+        } else {
+            self.v0_4
+        }
+    }
+}
+
+pub(super) const STARTUP_HOMING: VersionedPath = VersionedPath {
+    feature: "startup homing",
+    v0_4: None,
+    v0_5: Some("config.startup_homing"),
+    v0_6: Some("config.startup_homing"),
+};
+
+/// Replaced by `<axis>.config.enable_sensorless_mode` and closed loop control in 0.5.
+pub(super) const STARTUP_SENSORLESS_CONTROL: VersionedPath = VersionedPath {
+    feature: "startup sensorless control",
+    v0_4: Some("config.startup_sensorless_control"),
+    v0_5: None,
+    v0_6: None,
+};
+
+pub(super) const INPUT_MODE: VersionedPath = VersionedPath {
+    feature: "input modes",
+    v0_4: None,
+    v0_5: Some("controller.config.input_mode"),
+    v0_6: Some("controller.config.input_mode"),
+};
+
+/// Encoders are separate objects on 0.6, and the axis reads its estimates through a mapper.
+pub(super) const VELOCITY_ESTIMATE: VersionedPath = VersionedPath {
+    feature: "the velocity estimate",
+    v0_4: Some("encoder.vel_estimate"),
+    v0_5: Some("encoder.vel_estimate"),
+    v0_6: Some("pos_vel_mapper.vel"),
+};
+
+pub(super) const POSITION_ESTIMATE: VersionedPath = VersionedPath {
+    feature: "the position estimate",
+    v0_4: Some("encoder.pos_estimate"),
+    v0_5: Some("encoder.pos_estimate"),
+    v0_6: Some("pos_vel_mapper.pos_rel"),
+};
+
+/// The position the controller follows in position control. `pos_setpoint` became read-only in
+/// 0.5, and follows `input_pos` according to the input mode.
+pub(super) const POSITION_SETPOINT: VersionedPath = VersionedPath {
+    feature: "the position setpoint",
+    v0_4: Some("controller.pos_setpoint"),
+    v0_5: Some("controller.input_pos"),
+    v0_6: Some("controller.input_pos"),
+};
+
+// The motor configuration moved to `<axis>.config.motor` in 0.6.
+
+pub(super) const MOTOR_POLE_PAIRS: VersionedPath = VersionedPath {
+    feature: "the motor pole pairs",
+    v0_4: Some("motor.config.pole_pairs"),
+    v0_5: Some("motor.config.pole_pairs"),
+    v0_6: Some("config.motor.pole_pairs"),
+};
+
+pub(super) const MOTOR_RESISTANCE_CALIB_MAX_VOLTAGE: VersionedPath = VersionedPath {
+    feature: "the motor resistance calibration voltage",
+    v0_4: Some("motor.config.resistance_calib_max_voltage"),
+    v0_5: Some("motor.config.resistance_calib_max_voltage"),
+    v0_6: Some("config.motor.resistance_calib_max_voltage"),
+};
+
+/// The S1 and Pro have a fixed current sense range.
+pub(super) const MOTOR_REQUESTED_CURRENT_RANGE: VersionedPath = VersionedPath {
+    feature: "the requested current range",
+    v0_4: Some("motor.config.requested_current_range"),
+    v0_5: Some("motor.config.requested_current_range"),
+    v0_6: None,
+};
+
+pub(super) const MOTOR_CURRENT_CONTROL_BANDWIDTH: VersionedPath = VersionedPath {
+    feature: "the current control bandwidth",
+    v0_4: Some("motor.config.current_control_bandwidth"),
+    v0_5: Some("motor.config.current_control_bandwidth"),
+    v0_6: Some("config.motor.current_control_bandwidth"),
+};
+
+/// 0.6 keeps calibration results whenever the configuration is saved.
+pub(super) const MOTOR_PRE_CALIBRATED: VersionedPath = VersionedPath {
+    feature: "motor pre-calibration",
+    v0_4: Some("motor.config.pre_calibrated"),
+    v0_5: Some("motor.config.pre_calibrated"),
+    v0_6: None,
+};
+
+/// `MotorType::HighCurrent` is called `PMSM_CURRENT_CONTROL` on 0.6, with the same value.
+pub(super) const MOTOR_TYPE: VersionedPath = VersionedPath {
+    feature: "the motor type",
+    v0_4: Some("motor.config.motor_type"),
+    v0_5: Some("motor.config.motor_type"),
+    v0_6: Some("config.motor.motor_type"),
+};
+
+pub(super) const CURRENT_LIMIT: VersionedPath = VersionedPath {
+    feature: "current limit",
+    v0_4: Some("motor.config.current_lim"),
+    v0_5: Some("motor.config.current_lim"),
+    v0_6: Some("config.motor.current_soft_max"),
+};
+
+// Encoders are configured on the board rather than the axis in 0.6, except for the bandwidth.
+
+pub(super) const ENCODER_MODE: VersionedPath = VersionedPath {
+    feature: "the encoder mode",
+    v0_4: Some("encoder.config.mode"),
+    v0_5: Some("encoder.config.mode"),
+    v0_6: None,
+};
+
+pub(super) const ENCODER_CPR: VersionedPath = VersionedPath {
+    feature: "the encoder CPR",
+    v0_4: Some("encoder.config.cpr"),
+    v0_5: Some("encoder.config.cpr"),
+    v0_6: None,
+};
+
+pub(super) const ENCODER_BANDWIDTH: VersionedPath = VersionedPath {
+    feature: "the encoder bandwidth",
+    v0_4: Some("encoder.config.bandwidth"),
+    v0_5: Some("encoder.config.bandwidth"),
+    v0_6: Some("config.encoder_bandwidth"),
+};
+
+pub(super) const ENCODER_PRE_CALIBRATED: VersionedPath = VersionedPath {
+    feature: "encoder pre-calibration",
+    v0_4: Some("encoder.config.pre_calibrated"),
+    v0_5: Some("encoder.config.pre_calibrated"),
+    v0_6: None,
+};
+
+// 0.6 replaced the error registers of each component with `<axis>.active_errors`.
+
+pub(super) const AXIS_ERROR: VersionedPath = VersionedPath {
+    feature: "the axis error register",
+    v0_4: Some("error"),
+    v0_5: Some("error"),
+    v0_6: None,
+};
+
+pub(super) const MOTOR_ERROR: VersionedPath = VersionedPath {
+    feature: "the motor error register",
+    v0_4: Some("motor.error"),
+    v0_5: Some("motor.error"),
+    v0_6: None,
+};
+
+pub(super) const ENCODER_ERROR: VersionedPath = VersionedPath {
+    feature: "the encoder error register",
+    v0_4: Some("encoder.error"),
+    v0_5: Some("encoder.error"),
+    v0_6: None,
+};
+
+pub(super) const CONTROLLER_ERROR: VersionedPath = VersionedPath {
+    feature: "the controller error register",
+    v0_4: Some("controller.error"),
+    v0_5: Some("controller.error"),
+    v0_6: None,
+};
+
+pub(super) const ACTIVE_ERRORS: VersionedPath = VersionedPath {
+    feature: "active errors",
+    v0_4: None,
+    v0_5: None,
+    v0_6: Some("active_errors"),
+};
+
+pub(super) const DISARM_REASON: VersionedPath = VersionedPath {
+    feature: "disarm reasons",
+    v0_4: None,
+    v0_5: None,
+    v0_6: Some("disarm_reason"),
+};
+
+pub(super) const PROCEDURE_RESULT: VersionedPath = VersionedPath {
+    feature: "procedure results",
+    v0_4: None,
+    v0_5: None,
+    v0_6: Some("procedure_result"),
+};
+
+/// On 0.4, the watchdog is enabled by setting a timeout above zero.
+pub(super) const ENABLE_WATCHDOG: VersionedPath = VersionedPath {
+    feature: "enabling the watchdog",
+    v0_4: None,
+    v0_5: Some("config.enable_watchdog"),
+    v0_6: Some("config.enable_watchdog"),
+};
@@ -0,0 +1,116 @@
+//! Feeds the watchdog of one or more axes from a background thread.
+
+use std::io::{Error, Read, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::enumerations::AxisID;
+use crate::enumerations::errors::{ODriveError, ODriveResult};
+
+use super::ODrive;
+
+/// How many times `WatchdogKeepAlive::start` feeds the watchdog per timeout. Feeding several times
+/// leaves room for the `ODrive` being locked by a slow request.
+const FEEDS_PER_TIMEOUT: u32 = 4;
+
+/// Feeds the watchdog of one or more axes from a background thread until it is dropped.
+///
+/// The thread shares the `ODrive` with the rest of the program through a mutex, and only holds it
+/// while feeding. It stops at the first failed feed, or when the mutex is poisoned by a thread
+/// which panicked while holding it, so that the watchdog expires and disarms the axes rather than
+/// being kept alive by a program which is no longer in control.
+#[derive(Debug)]
+pub struct WatchdogKeepAlive {
+    interval: Duration,
+    running: Arc<AtomicBool>,
+    stop: Option<Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl WatchdogKeepAlive {
+    /// Starts feeding the watchdog of each axis at a quarter of the shortest
+    /// `<axis>.config.watchdog_timeout` among them.
+    ///
+    /// Returns `ODriveError::InvalidConfiguration` if no axes are given or a timeout is not a
+    /// finite value above zero.
+    pub fn start<T>(odrive: Arc<Mutex<ODrive<T>>>, axes: &[AxisID]) -> ODriveResult<Self>
+        where T: Read + Write + Send + 'static {
+        let mut shortest: Option<f32> = None;
+        {
+            let mut guard = odrive.lock().map_err(|_| poisoned())?;
+            for &axis in axes {
+                let timeout = guard.read_watchdog_timeout(axis)?;
+                if !timeout.is_finite() || timeout <= 0.0 {
+                    return Err(ODriveError::InvalidConfiguration(
+                        format!("the watchdog timeout of axis {} is {}", axis as u8, timeout)));
+                }
+                shortest = Some(shortest.map_or(timeout, |shortest| shortest.min(timeout)));
+            }
+        }
+
+        match shortest {
+            Some(timeout) => {
+                let interval = Duration::from_secs_f32(timeout) / FEEDS_PER_TIMEOUT;
+                Ok(Self::with_interval(odrive, axes, interval))
+            }
+            None => Err(ODriveError::InvalidConfiguration("no axes to feed".to_owned())),
+        }
+    }
+
+    /// Starts feeding the watchdog of each axis every `interval`, starting immediately. The
+    /// interval should be well below the watchdog timeout.
+    pub fn with_interval<T>(odrive: Arc<Mutex<ODrive<T>>>, axes: &[AxisID], interval: Duration) -> Self
+        where T: Read + Write + Send + 'static {
+        let axes = axes.to_vec();
+        let running = Arc::new(AtomicBool::new(true));
+        let (stop, stopped) = mpsc::channel();
+
+        let thread_running = Arc::clone(&running);
+        let thread = thread::spawn(move || {
+            loop {
+                let fed = match odrive.lock() {
+                    Ok(mut guard) => axes.iter().all(|&axis| guard.feed_watchdog(axis).is_ok()),
+                    Err(_) => false,
+                };
+                if !fed {
+                    break;
+                }
+                // A message or a disconnected sender both mean the keep-alive was dropped.
+                match stopped.recv_timeout(interval) {
+                    Err(RecvTimeoutError::Timeout) => continue,
+                    _ => break,
+                }
+            }
+            thread_running.store(false, Ordering::SeqCst);
+        });
+
+        Self { interval, running, stop: Some(stop), thread: Some(thread) }
+    }
+
+    /// The time between two feeds.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Returns `false` once the thread has stopped feeding because a feed failed.
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for WatchdogKeepAlive {
+    /// Stops feeding and waits for the thread to finish, so that no feed is sent after the drop.
+    fn drop(&mut self) {
+        self.stop.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn poisoned() -> ODriveError {
+    ODriveError::Io(Error::other("the ODrive mutex is poisoned"))
+}
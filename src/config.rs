@@ -0,0 +1,333 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{Read, Write};
+
+use crate::commands::ODrive;
+use crate::enumerations::errors::{ODriveError, ODriveResult};
+use crate::firmware::FirmwareVersion;
+use crate::properties::{config_properties, ConfigProperties, PropertyValue, ValueKind};
+
+/// The relative difference below which two floats are considered equal, to allow for the rounding
+/// of values sent through the ASCII protocol.
+const FLOAT_TOLERANCE: f64 = 1e-5;
+
+/// The absolute difference below which two floats are always considered equal, so that values
+/// close to zero are not compared by their rounding error alone.
+const FLOAT_ABSOLUTE_TOLERANCE: f64 = 1e-6;
+
+/// The value of a configurable property.
+///
+/// With the `serde` feature, values are serialized as plain booleans and numbers. Floats which are
+/// not finite, such as the default current limits of some firmware versions, are serialized as the
+/// strings `"inf"`, `"-inf"` and `"nan"`, which formats like JSON have no numbers for.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum ConfigValue {
+    Bool(bool),
+    /// Integers, and enumerations and error flags as their raw value.
+    Integer(i64),
+    Float(f32),
+}
+
+impl ConfigValue {
+    pub fn kind(&self) -> ValueKind {
+        match self {
+            ConfigValue::Bool(_) => ValueKind::Bool,
+            ConfigValue::Integer(_) => ValueKind::Integer,
+            ConfigValue::Float(_) => ValueKind::Float,
+        }
+    }
+
+    /// Compares two values, allowing for rounding if either is a float. Integers and floats are
+    /// compared by their numeric value, as a hand-written configuration may write `4` for `4.0`.
+    pub fn approx_eq(&self, other: &ConfigValue) -> bool {
+        match (self.as_f64(), other.as_f64()) {
+            (Some(a), Some(b)) if self.kind() == ValueKind::Float || other.kind() == ValueKind::Float => {
+                if a == b || (a.is_nan() && b.is_nan()) {
+                    true
+                } else if !a.is_finite() || !b.is_finite() {
+                    // Infinities and NaN are only equal to themselves, whatever the tolerance.
+                    false
+                } else {
+                    (a - b).abs() <= (FLOAT_TOLERANCE * a.abs().max(b.abs())).max(FLOAT_ABSOLUTE_TOLERANCE)
+                }
+            }
+            _ => self == other,
+        }
+    }
+
+    fn as_f64(&self) -> Option<f64> {
+        match *self {
+            ConfigValue::Bool(_) => None,
+            ConfigValue::Integer(value) => Some(value as f64),
+            ConfigValue::Float(value) => Some(f64::from(value)),
+        }
+    }
+
+    fn parse(kind: ValueKind, response: &str) -> Option<Self> {
+        match kind {
+            ValueKind::Bool => bool::parse(response).map(ConfigValue::Bool),
+            ValueKind::Integer => response.parse().ok().map(ConfigValue::Integer),
+            ValueKind::Float => response.parse().ok().map(ConfigValue::Float),
+        }
+    }
+}
+
+/// Formats the value for the ASCII protocol, which sends booleans as `1` and `0`.
+impl fmt::Display for ConfigValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigValue::Bool(value) => write!(f, "{}", *value as u8),
+            ConfigValue::Integer(value) => write!(f, "{}", value),
+            ConfigValue::Float(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ConfigValue {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match *self {
+            ConfigValue::Bool(value) => serializer.serialize_bool(value),
+            ConfigValue::Integer(value) => serializer.serialize_i64(value),
+            ConfigValue::Float(value) if value.is_nan() => serializer.serialize_str("nan"),
+            ConfigValue::Float(value) if value == f32::INFINITY => serializer.serialize_str("inf"),
+            ConfigValue::Float(value) if value == f32::NEG_INFINITY => serializer.serialize_str("-inf"),
+            ConfigValue::Float(value) => serializer.serialize_f32(value),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ConfigValue {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ConfigValueVisitor)
+    }
+}
+
+#[cfg(feature = "serde")]
+struct ConfigValueVisitor;
+
+#[cfg(feature = "serde")]
+impl serde::de::Visitor<'_> for ConfigValueVisitor {
+    type Value = ConfigValue;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a boolean, a number, or one of \"inf\", \"-inf\" and \"nan\"")
+    }
+
+    fn visit_bool<E: serde::de::Error>(self, value: bool) -> Result<ConfigValue, E> {
+        Ok(ConfigValue::Bool(value))
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<ConfigValue, E> {
+        Ok(ConfigValue::Integer(value))
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<ConfigValue, E> {
+        use std::convert::TryFrom;
+        i64::try_from(value).map(ConfigValue::Integer)
+            .map_err(|_| E::invalid_value(serde::de::Unexpected::Unsigned(value), &self))
+    }
+
+    fn visit_f64<E: serde::de::Error>(self, value: f64) -> Result<ConfigValue, E> {
+        Ok(ConfigValue::Float(value as f32))
+    }
+
+    fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<ConfigValue, E> {
+        match value {
+            "inf" => Ok(ConfigValue::Float(f32::INFINITY)),
+            "-inf" => Ok(ConfigValue::Float(f32::NEG_INFINITY)),
+            "nan" => Ok(ConfigValue::Float(f32::NAN)),
+            _ => Err(E::invalid_value(serde::de::Unexpected::Str(value), &self)),
+        }
+    }
+}
+
+/// The configurable properties of an axis or of the board, by path.
+pub type ConfigValues = BTreeMap<String, ConfigValue>;
+
+/// A snapshot of every configurable property of an ODrive, as read by
+/// `ODrive::read_config_snapshot`.
+///
+/// The configurable properties are the writable properties below a `config` object of the object
+/// model of the firmware, such as `config.dc_bus_overvoltage_trip_level` or
+/// `motor.config.pole_pairs`.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceConfig {
+    /// The firmware the snapshot was read from. Property paths depend on it.
+    pub firmware: FirmwareVersion,
+    /// The properties of the board, not including its axes.
+    pub board: ConfigValues,
+    /// The properties of each axis, by path relative to the axis, indexed by `AxisID`.
+    pub axes: Vec<ConfigValues>,
+}
+
+impl DeviceConfig {
+    /// Lists the properties of `desired` which differ from this configuration, see
+    /// `ConfigValue::approx_eq`. Properties which are missing from `desired` are left as they are.
+    pub fn diff(&self, desired: &DeviceConfig) -> Vec<ConfigDifference> {
+        let mut differences = Vec::new();
+        diff_values("", &self.board, &desired.board, &mut differences);
+        let empty = ConfigValues::new();
+        for (index, desired_axis) in desired.axes.iter().enumerate() {
+            let current = self.axes.get(index).unwrap_or(&empty);
+            diff_values(&format!("axis{}.", index), current, desired_axis, &mut differences);
+        }
+        differences
+    }
+}
+
+fn diff_values(prefix: &str, current: &ConfigValues, desired: &ConfigValues, differences: &mut Vec<ConfigDifference>) {
+    for (path, desired) in desired {
+        let current = current.get(path).copied();
+        if !current.is_some_and(|current| current.approx_eq(desired)) {
+            differences.push(ConfigDifference { path: format!("{}{}", prefix, path), current, desired: *desired });
+        }
+    }
+}
+
+/// A property whose value differs from the desired configuration.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ConfigDifference {
+    /// The full path of the property, such as `axis0.motor.config.pole_pairs`.
+    pub path: String,
+    /// The current value, or `None` if the property was missing from the current configuration.
+    pub current: Option<ConfigValue>,
+    pub desired: ConfigValue,
+}
+
+/// What happened to a property written by `ODrive::apply_config`.
+#[derive(Debug)]
+pub enum ApplyOutcome {
+    /// The property was written, and read back as the desired value.
+    Verified,
+    /// The property was written, but read back as a different value, for example because the
+    /// ODrive clamped it.
+    Mismatch(ConfigValue),
+    /// The property could not be written or read back, for example because it is unknown to the
+    /// firmware.
+    Failed(ODriveError),
+}
+
+/// A property written by `ODrive::apply_config`.
+#[derive(Debug)]
+pub struct AppliedProperty {
+    pub difference: ConfigDifference,
+    pub outcome: ApplyOutcome,
+}
+
+/// The properties written by `ODrive::apply_config`, in the order they were written.
+#[derive(Debug, Default)]
+pub struct ApplyReport {
+    pub properties: Vec<AppliedProperty>,
+}
+
+impl ApplyReport {
+    /// Returns true if every property was verified.
+    pub fn is_success(&self) -> bool {
+        self.failures().next().is_none()
+    }
+
+    /// The properties which were not verified.
+    pub fn failures(&self) -> impl Iterator<Item=&AppliedProperty> {
+        self.properties.iter().filter(|property| !matches!(property.outcome, ApplyOutcome::Verified))
+    }
+}
+
+/// # Configuration Snapshots
+/// A configuration is only compared with, or applied to, an ODrive running the same major and minor
+/// firmware version as the one it was read from, since the paths and units of properties change
+/// between them. Otherwise, `ODriveError::InvalidConfiguration` is returned.
+impl<T> ODrive<T> where T: Read + Write {
+    /// Reads every configurable property of the board and its axes.
+    ///
+    /// The properties are taken from the object model in `interface/` closest to the firmware of
+    /// the ODrive. Properties which the ODrive reports as unknown, because its firmware revision
+    /// does not have them, are left out of the snapshot.
+    pub fn read_config_snapshot(&mut self) -> ODriveResult<DeviceConfig> {
+        let firmware = self.firmware_version()?;
+        let (board_properties, axis_properties) = config_properties(firmware);
+
+        let board = self.read_config_values("", board_properties)?;
+        let mut axes = Vec::new();
+        for &axis in self.axes()? {
+            axes.push(self.read_config_values(&format!("axis{}.", axis as u8), axis_properties)?);
+        }
+        Ok(DeviceConfig { firmware, board, axes })
+    }
+
+    /// Reads the current configuration, and lists the properties of `desired` which differ from
+    /// it. See `DeviceConfig::diff`.
+    pub fn diff_config(&mut self, desired: &DeviceConfig) -> ODriveResult<Vec<ConfigDifference>> {
+        let current = self.read_config_snapshot()?;
+        if (current.firmware.major, current.firmware.minor) != (desired.firmware.major, desired.firmware.minor) {
+            return Err(ODriveError::InvalidConfiguration(format!(
+                "the configuration was read from firmware {}, but the ODrive runs {}", desired.firmware, current.firmware)));
+        }
+        Ok(current.diff(desired))
+    }
+
+    /// Writes the properties of `desired` which differ from the current configuration, and reads
+    /// each of them back to verify it. The configuration is not saved.
+    ///
+    /// A property which cannot be written or verified, for example because the ODrive did not
+    /// answer the read-back in time, is reported, and the remaining properties are still written.
+    /// Only I/O errors, after which nothing more can be written, are returned instead.
+    pub fn apply_config(&mut self, desired: &DeviceConfig) -> ODriveResult<ApplyReport> {
+        let mut report = ApplyReport::default();
+        for difference in self.diff_config(desired)? {
+            let outcome = match self.write_and_verify(&difference.path, difference.desired) {
+                Ok(value) if value.approx_eq(&difference.desired) => ApplyOutcome::Verified,
+                Ok(value) => ApplyOutcome::Mismatch(value),
+                Err(error @ ODriveError::Io(_)) => return Err(error),
+                Err(error) => ApplyOutcome::Failed(error),
+            };
+            report.properties.push(AppliedProperty { difference, outcome });
+        }
+        Ok(report)
+    }
+
+    /// Writes a property and returns the value read back from it, parsed as the kind the object
+    /// model gives the property rather than the kind of `value`.
+    fn write_and_verify(&mut self, path: &str, value: ConfigValue) -> ODriveResult<ConfigValue> {
+        match value {
+            ConfigValue::Bool(value) => self.set_config_property(path, value)?,
+            ConfigValue::Integer(value) => self.set_config_property(path, value)?,
+            ConfigValue::Float(value) => self.set_config_property(path, value)?,
+        }
+        let kind = property_kind(self.firmware_version()?, path).unwrap_or_else(|| value.kind());
+        let response = self.get_config_property(path)?;
+        match ConfigValue::parse(kind, &response) {
+            Some(value) => Ok(value),
+            None => Err(self.invalid_message(response)),
+        }
+    }
+
+    fn read_config_values(&mut self, prefix: &str, properties: ConfigProperties) -> ODriveResult<ConfigValues> {
+        let mut values = ConfigValues::new();
+        for &(path, kind) in properties {
+            let response = match self.get_config_property(&format!("{}{}", prefix, path)) {
+                Ok(response) => response,
+                Err(ODriveError::UnknownProperty(_)) => continue,
+                Err(error) => return Err(error),
+            };
+            match ConfigValue::parse(kind, &response) {
+                Some(value) => values.insert(path.to_owned(), value),
+                None => return Err(self.invalid_message(response)),
+            };
+        }
+        Ok(values)
+    }
+}
+
+/// Looks up the kind of a configurable property, such as `axis0.motor.config.current_lim`, in the
+/// object model of the given firmware.
+fn property_kind(firmware: FirmwareVersion, path: &str) -> Option<ValueKind> {
+    let (board_properties, axis_properties) = config_properties(firmware);
+    let (properties, path) = match path.strip_prefix("axis").and_then(|path| path.split_once('.')) {
+        Some((index, path)) if index.parse::<u8>().is_ok() => (axis_properties, path),
+        _ => (board_properties, path),
+    };
+    properties.iter().find(|&&(name, _)| name == path).map(|&(_, kind)| kind)
+}
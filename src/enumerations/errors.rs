@@ -1,4 +1,12 @@
+use std::error::Error;
+use std::fmt;
 use std::io;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use super::{AxisID, AxisState};
+use crate::firmware::FirmwareVersion;
 
 /// The `ODriveResult` type is used as a return type for operations which read to
 /// or write from the ODrive.
@@ -10,11 +18,129 @@ pub enum ODriveError {
     Motor(MotorError),
     Encoder(EncoderError),
     Controller(ControllerError),
+    /// Used when an axis returned to `Idle` with the contained errors set on `<axis>.error`.
+    AxisFailed(AxisID, AxisErrors),
+    /// Used when an axis did not finish the contained state before `Timeouts::state_change`.
+    StateTimedOut(AxisID, AxisState),
     /// Used when the ODrive sends us an invalid message.
     /// If you see this, file an issue.
-    InvalidMessageReceived(String),
-    NoMessageReceived,
-    Io(io::Error)
+    InvalidMessageReceived(String, RequestContext),
+    /// Used when the ODrive does not respond before the read timeout.
+    NoMessageReceived(RequestContext),
+    Io(io::Error),
+    /// Used when the responses read from the ODrive may no longer match the requests they belong
+    /// to, for example after garbage was received or a resynchronization failed.
+    Desynchronized(String),
+    /// The ODrive replied `invalid property` to the contained request.
+    UnknownProperty(String),
+    /// The ODrive replied `invalid command format` or `invalid motor` to the contained request.
+    MalformedCommand(String),
+    /// The ODrive replied `unknown command` to the contained request.
+    UnknownCommand(String),
+    /// Used when a requested configuration is rejected before being sent to the ODrive.
+    InvalidConfiguration(String),
+    /// Used when a feature does not exist on the firmware of the connected ODrive.
+    Unsupported(String, FirmwareVersion),
+    /// Used when the contained properties read differently after saving the configuration.
+    NotPersisted(Vec<String>),
+    /// Returned from a configuration transaction to abort it, with the reason.
+    TransactionAborted(String),
+    /// Used when a configuration transaction failed with the contained error, and the listed
+    /// properties could not be restored afterwards.
+    RollbackFailed(Box<ODriveError>, Vec<String>),
+}
+
+impl fmt::Display for ODriveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ODriveError::Axis(error) => write!(f, "axis error: {:?}", error),
+            ODriveError::Motor(error) => write!(f, "motor error: {:?}", error),
+            ODriveError::Encoder(error) => write!(f, "encoder error: {:?}", error),
+            ODriveError::Controller(error) => write!(f, "controller error: {:?}", error),
+            ODriveError::AxisFailed(axis, errors) => write!(f, "{:?} failed: {:?}", axis, errors),
+            ODriveError::StateTimedOut(axis, state) => write!(f, "{:?} timed out in state {:?}", axis, state),
+            ODriveError::InvalidMessageReceived(message, context) => {
+                write!(f, "received invalid response `{}` to {}", message, context)
+            }
+            ODriveError::NoMessageReceived(context) => write!(f, "received no response to {}", context),
+            ODriveError::Io(error) => write!(f, "I/O error: {}", error),
+            ODriveError::Desynchronized(reason) => write!(f, "connection out of sync: {}", reason),
+            ODriveError::UnknownProperty(request) => write!(f, "unknown property in `{}`", request),
+            ODriveError::MalformedCommand(request) => write!(f, "malformed command `{}`", request),
+            ODriveError::UnknownCommand(request) => write!(f, "unknown command `{}`", request),
+            ODriveError::InvalidConfiguration(reason) => write!(f, "invalid configuration: {}", reason),
+            ODriveError::Unsupported(feature, firmware) => write!(f, "{} is not supported by firmware {}", feature, firmware),
+            ODriveError::NotPersisted(paths) => write!(f, "not persisted by saving: {}", paths.join(", ")),
+            ODriveError::TransactionAborted(reason) => write!(f, "transaction aborted: {}", reason),
+            ODriveError::RollbackFailed(error, paths) => {
+                write!(f, "{}, and could not restore: {}", error, paths.join(", "))
+            }
+        }
+    }
+}
+
+impl Error for ODriveError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ODriveError::Io(error) => Some(error),
+            ODriveError::RollbackFailed(error, _) => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ODriveError {
+    fn from(error: io::Error) -> Self {
+        ODriveError::Io(error)
+    }
+}
+
+/// Describes the request an error occurred in response to.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct RequestContext {
+    /// The ASCII command which was sent, without the trailing newline.
+    pub request: String,
+    /// The axis the request addressed, if any.
+    pub axis: Option<AxisID>,
+    /// The time between sending the request and the error occurring.
+    pub elapsed: Duration,
+}
+
+impl RequestContext {
+    /// Creates a context for `request`, working out which axis it addressed from its text.
+    pub fn new(request: String, elapsed: Duration) -> Self {
+        let axis = request_axis(&request);
+        Self { request, axis, elapsed }
+    }
+}
+
+impl fmt::Display for RequestContext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`{}`", self.request)?;
+        if let Some(axis) = self.axis {
+            write!(f, " on axis {}", axis as u8)?;
+        }
+        write!(f, " after {:?}", self.elapsed)
+    }
+}
+
+/// Finds the axis addressed by an ASCII command, either through a property path such as
+/// `r axis1.error` or through the first argument of a command such as `v 1 10 0`.
+fn request_axis(request: &str) -> Option<AxisID> {
+    let mut words = request.split_whitespace();
+    let command = words.next()?;
+    let argument = words.next()?;
+    let axis = if command == "r" || command == "w" {
+        argument.strip_prefix("axis")?.split('.').next()?
+    } else {
+        argument
+    };
+
+    match axis {
+        "0" => Some(AxisID::Zero),
+        "1" => Some(AxisID::One),
+        _ => None,
+    }
 }
 
 #[repr(u16)]
@@ -73,4 +199,180 @@ pub enum EncoderError {
 pub enum ControllerError {
     ErrorNone = 0,
     ErrorOverspeed = 0x01,
-}
\ No newline at end of file
+}
+
+/// The errors of an axis on firmware 0.6 and later, which replaced the error registers of the
+/// axis, motor, encoder and controller with a single register, `<axis>.active_errors`.
+/// `<axis>.disarm_reason` holds the same flags.
+pub use crate::properties::fw_0_6::BoardError as ActiveError;
+
+/// Implemented by the error enums whose variants are single bits of an ODrive error register.
+pub trait ErrorFlag: Copy + 'static {
+    /// Every known flag, excluding the `ErrorNone` variant.
+    const FLAGS: &'static [Self];
+
+    /// The bit (or bits) of the register which this flag occupies.
+    fn bits(self) -> u64;
+}
+
+macro_rules! impl_error_flag {
+    ($flag:ty, [$($variant:ident),* $(,)?]) => {
+        impl ErrorFlag for $flag {
+            const FLAGS: &'static [Self] = &[$(<$flag>::$variant),*];
+
+            fn bits(self) -> u64 {
+                self as u64
+            }
+        }
+    };
+}
+
+impl_error_flag!(AxisError, [
+    ErrorInvalidState, ErrorDcBusUnderVoltage, ErrorDcBusOverVoltage, ErrorCurrentMeasurementTimeout,
+    ErrorBrakeResistorDisarmed, ErrorMotorDisarmed, ErrorMotorFailed, ErrorSensorlessEstimatorFailed,
+    ErrorEncoderFailed, ErrorControllerFailed, ErrorPosCtrlDuringSensorless, ErrorWatchdogTimerExpired,
+]);
+
+impl_error_flag!(MotorError, [
+    ErrorPhaseResistanceOutOfRange, ErrorPhaseInductanceOutOfRange, ErrorAdcFailed, ErrorDrvFault,
+    ErrorControlDeadlineMissed, ErrorNotImplementedMotorType, ErrorBrakeCurrentOutOfRange,
+    ErrorModulationMagnitude, ErrorBrakeDeadTimeViolation, ErrorUnexpectedTimerCallback,
+    ErrorCurrentSenseSaturation, ErrorCurrentUnstable,
+]);
+
+impl_error_flag!(EncoderError, [
+    ErrorUnstableGain, ErrorCprOutOfRange, ErrorNoResponse, ErrorUnsupportedEncoderMode,
+    ErrorIllegalHallState, ErrorIndexNotFoundYet,
+]);
+
+impl_error_flag!(ControllerError, [
+    ErrorOverspeed,
+]);
+
+/// A set of error flags, as read from one of the ODrive's error registers.
+///
+/// The ODrive reports errors as an OR-ed bitmask, so a register can hold several errors at once.
+/// Bits which do not correspond to a known flag are preserved and can be inspected with
+/// `unknown_bits`.
+#[derive(Eq, PartialEq, Hash, Copy, Clone)]
+pub struct ErrorSet<E> {
+    bits: u64,
+    flags: PhantomData<E>,
+}
+
+/// The decoded value of `<axis>.error`.
+pub type AxisErrors = ErrorSet<AxisError>;
+/// The decoded value of `<axis>.motor.error`.
+pub type MotorErrors = ErrorSet<MotorError>;
+/// The decoded value of `<axis>.encoder.error`.
+pub type EncoderErrors = ErrorSet<EncoderError>;
+/// The decoded value of `<axis>.controller.error`.
+pub type ControllerErrors = ErrorSet<ControllerError>;
+/// The decoded value of `<axis>.active_errors` or `<axis>.disarm_reason`, on firmware 0.6 and later.
+pub type ActiveErrors = ErrorSet<ActiveError>;
+
+impl<E> ErrorSet<E> where E: ErrorFlag {
+    /// Creates a set from the raw register value, keeping any unknown bits.
+    pub fn from_bits(bits: u64) -> Self {
+        Self { bits, flags: PhantomData }
+    }
+
+    /// Creates a set with no errors in it.
+    pub fn empty() -> Self {
+        Self::from_bits(0)
+    }
+
+    /// The raw register value.
+    pub fn bits(&self) -> u64 {
+        self.bits
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// Returns true if every bit of `flag` is set.
+    /// Checking for an `ErrorNone` flag is equivalent to calling `is_empty`.
+    pub fn contains(&self, flag: E) -> bool {
+        let bits = flag.bits();
+        if bits == 0 {
+            self.is_empty()
+        } else {
+            self.bits & bits == bits
+        }
+    }
+
+    pub fn insert(&mut self, flag: E) {
+        self.bits |= flag.bits();
+    }
+
+    /// The bits of the register which do not correspond to any flag known by this library.
+    pub fn unknown_bits(&self) -> u64 {
+        E::FLAGS.iter().fold(self.bits, |bits, flag| bits & !flag.bits())
+    }
+
+    /// Iterates over the known flags contained in this set.
+    pub fn iter(&self) -> impl Iterator<Item=E> {
+        let bits = self.bits;
+        E::FLAGS.iter().copied().filter(move |flag| bits & flag.bits() == flag.bits())
+    }
+}
+
+impl<E> Default for ErrorSet<E> where E: ErrorFlag {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<E> From<E> for ErrorSet<E> where E: ErrorFlag {
+    fn from(flag: E) -> Self {
+        Self::from_bits(flag.bits())
+    }
+}
+
+impl<E> FromIterator<E> for ErrorSet<E> where E: ErrorFlag {
+    fn from_iter<I: IntoIterator<Item=E>>(iter: I) -> Self {
+        let mut set = Self::empty();
+        for flag in iter {
+            set.insert(flag);
+        }
+        set
+    }
+}
+
+impl<E> fmt::Debug for ErrorSet<E> where E: ErrorFlag + fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut list = f.debug_set();
+        list.entries(self.iter());
+        let unknown = self.unknown_bits();
+        if unknown != 0 {
+            list.entry(&format_args!("{:#x}", unknown));
+        }
+        list.finish()
+    }
+}
+
+/// Every error register of a single axis, as returned by `ODrive::read_all_errors`.
+///
+/// Firmware 0.6 replaced the registers of the axis, motor, encoder and controller with
+/// `active_errors` and `disarm_reason`. The registers which the firmware does not have are left
+/// empty.
+#[derive(Debug, Default, Eq, PartialEq, Copy, Clone)]
+pub struct AxisErrorReport {
+    pub axis: AxisErrors,
+    pub motor: MotorErrors,
+    pub encoder: EncoderErrors,
+    pub controller: ControllerErrors,
+    /// `<axis>.active_errors`, on firmware 0.6 and later.
+    pub active: ActiveErrors,
+    /// `<axis>.disarm_reason`, on firmware 0.6 and later.
+    pub disarm_reason: ActiveErrors,
+}
+
+impl AxisErrorReport {
+    /// Returns true if none of the axis' error registers have any bits set.
+    pub fn is_empty(&self) -> bool {
+        self.axis.is_empty() && self.motor.is_empty() && self.encoder.is_empty() && self.controller.is_empty()
+            && self.active.is_empty() && self.disarm_reason.is_empty()
+    }
+}
@@ -1,11 +1,48 @@
 //! This file was derived from [the python library](https://github.com/madcowswe/ODrive/blob/master/tools/odrive/enums.py)
 
+use std::convert::TryFrom;
+
+use crate::firmware::FirmwareVersion;
+use crate::properties::PropertyValue;
+
 /// Contains error enums that can be sent from the ODrive.
 ///
 /// At the current moment, error handling is not fully implemented, so not all errors received from
 /// the ODrive will be caught.
 pub mod errors;
 
+/// Implements `TryFrom` for converting the raw value of an enumeration, as sent by the ODrive,
+/// back into the enumeration. The unrecognized value is returned on failure.
+///
+/// Also implements `PropertyValue`, so the enumeration can be read from and written to properties
+/// as its raw value.
+macro_rules! impl_try_from {
+    ($name:ident, $repr:ty, [$($variant:ident),* $(,)?]) => {
+        impl TryFrom<$repr> for $name {
+            type Error = $repr;
+
+            fn try_from(value: $repr) -> Result<Self, Self::Error> {
+                $(
+                    if value == $name::$variant as $repr {
+                        return Ok($name::$variant);
+                    }
+                )*
+                Err(value)
+            }
+        }
+
+        impl PropertyValue for $name {
+            fn parse(response: &str) -> Option<Self> {
+                response.parse::<$repr>().ok().and_then(|value| $name::try_from(value).ok())
+            }
+
+            fn format(&self) -> String {
+                (*self as $repr).to_string()
+            }
+        }
+    };
+}
+
 /// Used to indicate one of the two motors controlled by the ODrive.
 #[repr(u8)]
 #[derive(Debug, Ord, PartialOrd, Eq, PartialEq, Copy, Clone)]
@@ -28,6 +65,17 @@ pub enum AxisID {
 /// > 6. `EncoderIndexSearch`
 /// > 7. `EncoderOffsetCalibration`
 /// > 8. `ClosedLoopControl`
+///
+/// Later firmware versions add:
+///
+/// 9. `LockinSpin`
+/// 10. `EncoderDirFind`
+/// 11. `Homing`
+/// 12. `EncoderHallPolarityCalibration`
+/// 13. `EncoderHallPhaseCalibration`
+/// 14. `AnticoggingCalibration`
+///
+/// Not every state exists on every firmware version, see `AxisState::is_supported_by`.
 #[repr(u8)]
 #[derive(Debug, Ord, PartialOrd, Eq, PartialEq, Copy, Clone)]
 pub enum AxisState {
@@ -67,14 +115,76 @@ pub enum AxisState {
     /// Can only be entered if the motor is calibrated (`<axis>.motor.is_calibrated`)
     /// and the encoder is ready (`<axis>.encoder.is_ready`).
     ClosedLoopControl = 8,
+    /// Run the lock-in spin, which turns the motor open loop at the configured current.
+    LockinSpin = 9,
+    /// Turn the motor to find the direction of the encoder and store it in
+    /// `<axis>.encoder.config.direction`.
+    EncoderDirFind = 10,
+    /// Move towards the minimum endstop until it is triggered, then to its offset.
+    ///
+    /// Only available on firmware 0.5 and later.
+    Homing = 11,
+    /// Rotate the motor in lock-in to calibrate the polarity of the hall sensors.
+    ///
+    /// Only available on firmware 0.5 and later.
+    EncoderHallPolarityCalibration = 12,
+    /// Rotate the motor for 30 seconds to calibrate the edges of the hall sensors.
+    ///
+    /// Only available on firmware 0.5 and later.
+    EncoderHallPhaseCalibration = 13,
+    /// Measure the cogging torque of the motor.
+    ///
+    /// Only available on firmware 0.6 and later.
+    AnticoggingCalibration = 14,
+}
+
+impl AxisState {
+    /// Returns true if the axis remains in this state once it has been entered.
+    ///
+    /// Other states, such as the calibration states, run a procedure to completion and then
+    /// return the axis to `Idle`.
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, AxisState::Idle | AxisState::SensorlessControl | AxisState::ClosedLoopControl)
+    }
+
+    /// Returns true if the state exists on the given firmware version.
+    pub fn is_supported_by(&self, firmware: FirmwareVersion) -> bool {
+        match self {
+            AxisState::SensorlessControl => firmware < FirmwareVersion::new(0, 5, 0),
+            AxisState::Homing
+            | AxisState::EncoderHallPolarityCalibration
+            | AxisState::EncoderHallPhaseCalibration => firmware >= FirmwareVersion::new(0, 5, 0),
+            AxisState::AnticoggingCalibration => firmware >= FirmwareVersion::new(0, 6, 0),
+            _ => true,
+        }
+    }
+
+    /// Returns true if the state is missing from some of the supported firmware versions.
+    pub(crate) fn depends_on_firmware(&self) -> bool {
+        !self.is_supported_by(FirmwareVersion::new(0, 4, 0)) || !self.is_supported_by(FirmwareVersion::new(0, 6, 0))
+    }
 }
 
 #[repr(u8)]
 #[derive(Debug, Ord, PartialOrd, Eq, PartialEq, Copy, Clone)]
 pub enum MotorType {
     HighCurrent = 0,
+    /// Only available on firmware 0.4.
     LowCurrent = 1,
     MotorTypeGimbal = 2,
+    /// An AC induction motor. Only available on firmware 0.5 and later.
+    Acim = 3,
+}
+
+impl MotorType {
+    /// Returns true if the motor type exists on the given firmware version.
+    pub fn is_supported_by(&self, firmware: FirmwareVersion) -> bool {
+        match self {
+            MotorType::LowCurrent => firmware < FirmwareVersion::new(0, 5, 0),
+            MotorType::Acim => firmware >= FirmwareVersion::new(0, 5, 0),
+            _ => true,
+        }
+    }
 }
 
 #[repr(u8)]
@@ -87,9 +197,65 @@ pub enum ControlMode {
     TrajectoryControl = 4,
 }
 
-#[repr(u8)]
+/// The encoder modes of firmware 0.4 and 0.5. Firmware 0.6 configures encoders on the board
+/// rather than the axis.
+#[repr(u16)]
 #[derive(Debug, Ord, PartialOrd, Eq, PartialEq, Copy, Clone)]
 pub enum EncoderMode {
     EncoderModeIncremental = 0,
     EncoderModeHall = 1,
-}
\ No newline at end of file
+    EncoderModeSincos = 2,
+    /// A CUI AMT23 absolute encoder over SPI.
+    EncoderModeSpiAbsCui = 0x100,
+    /// An AMS AS5047 or AS5048 absolute encoder over SPI.
+    EncoderModeSpiAbsAms = 0x101,
+    /// A Broadcom AEAT-8800 absolute encoder over SPI.
+    EncoderModeSpiAbsAeat = 0x102,
+    /// An RLS absolute encoder over SPI. Only available on firmware 0.5 and later.
+    EncoderModeSpiAbsRls = 0x103,
+    /// An MPS MA732 absolute encoder over SPI. Only available on firmware 0.5 and later.
+    EncoderModeSpiAbsMa732 = 0x104,
+}
+
+impl EncoderMode {
+    /// Returns true if the encoder mode exists on the given firmware version.
+    pub fn is_supported_by(&self, firmware: FirmwareVersion) -> bool {
+        match self {
+            EncoderMode::EncoderModeSpiAbsRls | EncoderMode::EncoderModeSpiAbsMa732 => {
+                firmware >= FirmwareVersion::new(0, 5, 0)
+            }
+            _ => true,
+        }
+    }
+}
+
+/// The outcome of the most recent state of an axis, as reported by `<axis>.procedure_result` on
+/// firmware 0.6 and later.
+pub use crate::properties::fw_0_6::ProcedureResult;
+
+impl ProcedureResult {
+    /// Returns true if the procedure finished successfully.
+    pub fn is_success(&self) -> bool {
+        *self == ProcedureResult::Success
+    }
+}
+
+impl_try_from!(ControlMode, u8, [
+    VoltageControl, CurrentControl, VelocityControl, PositionControl, TrajectoryControl,
+]);
+
+impl_try_from!(EncoderMode, u16, [
+    EncoderModeIncremental, EncoderModeHall, EncoderModeSincos, EncoderModeSpiAbsCui, EncoderModeSpiAbsAms,
+    EncoderModeSpiAbsAeat, EncoderModeSpiAbsRls, EncoderModeSpiAbsMa732,
+]);
+
+impl_try_from!(AxisState, u8, [
+    Undefined, Idle, StartupSequence, FullCalibrationSequence, MotorCalibration,
+    SensorlessControl, EncoderIndexSearch, EncoderOffsetCalibration, ClosedLoopControl, LockinSpin,
+    EncoderDirFind, Homing, EncoderHallPolarityCalibration, EncoderHallPhaseCalibration, AnticoggingCalibration,
+]);
+
+impl_try_from!(MotorType, u8, [
+    HighCurrent, LowCurrent, MotorTypeGimbal, Acim,
+]);
+
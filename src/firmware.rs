@@ -0,0 +1,53 @@
+use std::fmt;
+
+/// The firmware version reported by an ODrive through `fw_version_major`, `fw_version_minor` and
+/// `fw_version_revision`.
+#[derive(Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FirmwareVersion {
+    pub major: u8,
+    pub minor: u8,
+    pub revision: u8,
+}
+
+impl FirmwareVersion {
+    pub const fn new(major: u8, minor: u8, revision: u8) -> Self {
+        Self { major, minor, revision }
+    }
+
+    /// Returns true if positions and velocities are expressed in turns rather than encoder counts.
+    /// The units changed in firmware 0.5.0.
+    pub fn uses_turns(&self) -> bool {
+        *self >= FirmwareVersion::new(0, 5, 0)
+    }
+
+    /// Returns true if the controller is commanded in torque (Nm) rather than current (A).
+    /// This changed in firmware 0.5.0, along with the meaning of the `c` ASCII command.
+    pub fn uses_torque_setpoints(&self) -> bool {
+        *self >= FirmwareVersion::new(0, 5, 0)
+    }
+
+    /// Returns true if the ODrive reboots after saving its configuration, which closes a USB
+    /// connection. This changed in firmware 0.5.0.
+    pub fn reboots_on_save(&self) -> bool {
+        *self >= FirmwareVersion::new(0, 5, 0)
+    }
+
+    /// Returns true if the `es` ASCII command sets the position estimate in turns, rather than the
+    /// linear count of the encoder. Encoders are no longer part of the axis from firmware 0.6.0.
+    pub fn sets_position_in_turns(&self) -> bool {
+        *self >= FirmwareVersion::new(0, 6, 0)
+    }
+
+    /// Returns true if the firmware understands the `sc` (clear errors) ASCII command.
+    /// The command was introduced in firmware 0.5.2.
+    pub fn supports_clear_errors_command(&self) -> bool {
+        *self >= FirmwareVersion::new(0, 5, 2)
+    }
+}
+
+impl fmt::Display for FirmwareVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.revision)
+    }
+}
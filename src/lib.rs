@@ -6,12 +6,30 @@ pub mod commands;
 /// errors.
 pub mod enumerations;
 
+/// The `properties` module contains the typed property tree of the ODrive object model, generated
+/// from the interface definitions in `interface/`, with one module per firmware version, such as
+/// `properties::fw_0_5::axis`. `ODrive::axis` and `ODrive::board` give the tree of firmware 0.4.
+pub mod properties;
+
+/// The `firmware` module contains types describing the firmware running on an ODrive.
+pub mod firmware;
+
+/// The `config` module contains snapshots of the configuration of an ODrive, which can be
+/// serialized with the `serde` feature.
+pub mod config;
+
 #[cfg(test)]
 #[cfg_attr(tarpaulin, skip)]
 mod test_stream;
 
 pub mod prelude {
-    pub use crate::commands::ODrive;
-    pub use crate::enumerations::{AxisID, AxisState, EncoderMode, ControlMode, MotorType};
+    pub use crate::commands::{Feedback, ODrive, SaveError, SaveOptions, StartupConfig, StateOutcome, SyncOptions,
+                              Timeouts, WatchdogKeepAlive};
+    pub use crate::firmware::FirmwareVersion;
+    pub use crate::config::{ApplyOutcome, ApplyReport, ConfigDifference, ConfigValue, ConfigValues, DeviceConfig};
+    pub use crate::enumerations::{AxisID, AxisState, EncoderMode, ControlMode, MotorType, ProcedureResult};
     pub use crate::enumerations::errors::{ODriveError, EncoderError, AxisError, ControllerError, MotorError, ODriveResult};
+    pub use crate::enumerations::errors::{ErrorSet, AxisErrors, MotorErrors, EncoderErrors, ControllerErrors, AxisErrorReport};
+    pub use crate::enumerations::errors::{ActiveError, ActiveErrors};
+    pub use crate::enumerations::errors::RequestContext;
 }
\ No newline at end of file
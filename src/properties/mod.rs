@@ -0,0 +1,301 @@
+use std::io::{Read, Write};
+use std::marker::PhantomData;
+
+use crate::commands::ODrive;
+use crate::enumerations::AxisID;
+use crate::enumerations::errors::{ErrorFlag, ErrorSet, ODriveResult};
+use crate::firmware::FirmwareVersion;
+
+/// Marks a property which can only be read.
+#[derive(Debug, Copy, Clone)]
+pub struct ReadOnly;
+
+/// Marks a property which can be both read and written.
+#[derive(Debug, Copy, Clone)]
+pub struct ReadWrite;
+
+/// Implemented by the access markers of properties which can be read.
+pub trait Readable {}
+
+/// Implemented by the access markers of properties which can be written.
+pub trait Writable {}
+
+impl Readable for ReadOnly {}
+
+impl Readable for ReadWrite {}
+
+impl Writable for ReadWrite {}
+
+/// A type which can be stored in a property, using the representation of the ASCII protocol.
+pub trait PropertyValue: Sized {
+    /// Parses the response to a read request, returning `None` if it is not a valid value.
+    fn parse(response: &str) -> Option<Self>;
+
+    /// Formats the value for a write request.
+    fn format(&self) -> String;
+}
+
+macro_rules! impl_numeric_value {
+    ($($ty:ty),*) => {
+        $(
+            impl PropertyValue for $ty {
+                fn parse(response: &str) -> Option<Self> {
+                    response.parse().ok()
+                }
+
+                fn format(&self) -> String {
+                    self.to_string()
+                }
+            }
+        )*
+    };
+}
+
+impl_numeric_value!(f32, f64, u8, u16, u32, u64, i8, i16, i32, i64);
+
+impl PropertyValue for bool {
+    fn parse(response: &str) -> Option<Self> {
+        match response {
+            "0" | "false" => Some(false),
+            "1" | "true" => Some(true),
+            _ => None,
+        }
+    }
+
+    fn format(&self) -> String {
+        (*self as u8).to_string()
+    }
+}
+
+/// The response as it was received, for properties without a more specific type.
+impl PropertyValue for String {
+    fn parse(response: &str) -> Option<Self> {
+        Some(response.to_owned())
+    }
+
+    fn format(&self) -> String {
+        self.clone()
+    }
+}
+
+impl<E: ErrorFlag> PropertyValue for ErrorSet<E> {
+    fn parse(response: &str) -> Option<Self> {
+        response.parse().ok().map(ErrorSet::from_bits)
+    }
+
+    fn format(&self) -> String {
+        self.bits().to_string()
+    }
+}
+
+/// How the value of a configurable property is stored in a `DeviceConfig`.
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub enum ValueKind {
+    Bool,
+    /// Integers, and enumerations and error flags as their raw value.
+    Integer,
+    Float,
+}
+
+/// Paths of configurable properties, with the kind of their value.
+pub type ConfigProperties = &'static [(&'static str, ValueKind)];
+
+/// A single property of the ODrive, such as `axis0.motor.config.current_lim`.
+///
+/// The value type `V` and the access `A` are fixed by the object model, so a property can only
+/// be read or written if the firmware allows it.
+pub struct Property<'a, T: Read, V, A> {
+    odrive: &'a mut ODrive<T>,
+    path: String,
+    marker: PhantomData<(V, A)>,
+}
+
+impl<'a, T: Read, V, A> Property<'a, T, V, A> {
+    pub(crate) fn new(odrive: &'a mut ODrive<T>, path: String) -> Self {
+        Self { odrive, path, marker: PhantomData }
+    }
+
+    /// The full path of the property, as used by the ASCII protocol.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+impl<'a, T, V, A> Property<'a, T, V, A> where T: Read + Write, V: PropertyValue, A: Readable {
+    /// Reads the current value of the property.
+    pub fn read(&mut self) -> ODriveResult<V> {
+        let response = self.odrive.get_config_property(&self.path)?;
+        match V::parse(&response) {
+            Some(value) => Ok(value),
+            None => Err(self.odrive.invalid_message(response)),
+        }
+    }
+}
+
+impl<'a, T, V, A> Property<'a, T, V, A> where T: Read + Write, V: PropertyValue, A: Writable {
+    /// Writes a new value to the property.
+    pub fn write(&mut self, value: V) -> ODriveResult<()> {
+        self.odrive.set_config_property(&self.path, value)
+    }
+}
+
+/// Joins the path of an object with the name of one of its members.
+fn join(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_owned()
+    } else {
+        format!("{}.{}", path, name)
+    }
+}
+
+/// Defines an object of the ODrive object model, with accessors for its child objects and
+/// properties. A member can be given an explicit name when its firmware name is not a valid
+/// method name.
+macro_rules! property_object {
+    (
+        $(#[$meta:meta])*
+        $name:ident {
+            objects {
+                $($(#[$child_meta:meta])* $child:ident $(= $child_name:literal)?: $child_type:ident,)*
+            }
+            properties {
+                $($(#[$property_meta:meta])* $property:ident $(= $property_name:literal)?: $value:ty, $access:ident;)*
+            }
+        }
+    ) => {
+        $(#[$meta])*
+        pub struct $name<'a, T: std::io::Read> {
+            odrive: &'a mut $crate::commands::ODrive<T>,
+            path: String,
+        }
+
+        impl<'a, T: std::io::Read> $name<'a, T> {
+            #[allow(dead_code)]
+            pub(crate) fn new(odrive: &'a mut $crate::commands::ODrive<T>, path: String) -> Self {
+                Self { odrive, path }
+            }
+
+            /// The full path of the object, as used by the ASCII protocol.
+            pub fn path(&self) -> &str {
+                &self.path
+            }
+
+            $(
+                $(#[$child_meta])*
+                pub fn $child(self) -> $child_type<'a, T> {
+                    let path = $crate::properties::join(&self.path, member_name!($child $(, $child_name)?));
+                    $child_type::new(self.odrive, path)
+                }
+            )*
+
+            $(
+                $(#[$property_meta])*
+                pub fn $property(self) -> $crate::properties::Property<'a, T, $value, $access> {
+                    let path = $crate::properties::join(&self.path, member_name!($property $(, $property_name)?));
+                    $crate::properties::Property::new(self.odrive, path)
+                }
+            )*
+        }
+    };
+}
+
+macro_rules! member_name {
+    ($member:ident) => { stringify!($member) };
+    ($member:ident, $name:literal) => { $name };
+}
+
+/// Defines an enumeration of the object model, which is sent as its raw value.
+macro_rules! property_enum {
+    (
+        $(#[$meta:meta])*
+        $name:ident: $repr:ident {
+            $($(#[$variant_meta:meta])* $variant:ident = $value:literal,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[allow(clippy::enum_variant_names)]
+        #[repr($repr)]
+        #[derive(Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Copy, Clone)]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant = $value,)*
+        }
+
+        impl std::convert::TryFrom<$repr> for $name {
+            type Error = $repr;
+
+            fn try_from(value: $repr) -> Result<Self, Self::Error> {
+                match value {
+                    $($value => Ok($name::$variant),)*
+                    _ => Err(value),
+                }
+            }
+        }
+
+        impl $crate::properties::PropertyValue for $name {
+            fn parse(response: &str) -> Option<Self> {
+                use std::convert::TryFrom;
+                response.parse::<$repr>().ok().and_then(|value| $name::try_from(value).ok())
+            }
+
+            fn format(&self) -> String {
+                (*self as $repr).to_string()
+            }
+        }
+    };
+}
+
+/// Defines a set of error flags of the object model, for use with `ErrorSet`.
+macro_rules! property_flags {
+    (
+        $(#[$meta:meta])*
+        $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident = $bits:literal,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[allow(clippy::enum_variant_names)]
+        #[repr(u64)]
+        #[derive(Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Copy, Clone)]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant = $bits,)*
+        }
+
+        impl $crate::enumerations::errors::ErrorFlag for $name {
+            const FLAGS: &'static [Self] = &[$($name::$variant),*];
+
+            fn bits(self) -> u64 {
+                self as u64
+            }
+        }
+    };
+}
+
+include!(concat!(env!("OUT_DIR"), "/interfaces.rs"));
+
+/// The configurable properties of the board and of an axis on the given firmware, taken from the
+/// closest object model.
+pub(crate) fn config_properties(firmware: FirmwareVersion) -> (ConfigProperties, ConfigProperties) {
+    if firmware >= FirmwareVersion::new(0, 6, 0) {
+        (fw_0_6::BOARD_CONFIG, fw_0_6::AXIS_CONFIG)
+    } else if firmware >= FirmwareVersion::new(0, 5, 0) {
+        (fw_0_5::BOARD_CONFIG, fw_0_5::AXIS_CONFIG)
+    } else {
+        (fw_0_4::BOARD_CONFIG, fw_0_4::AXIS_CONFIG)
+    }
+}
+
+/// # Property Tree
+///
+/// The tree follows the object model of firmware 0.4. The object models of other firmware
+/// versions are available from their own modules, such as `fw_0_5::axis`.
+impl<T> ODrive<T> where T: Read {
+    /// The root of the object model, holding the board level properties.
+    pub fn board(&mut self) -> fw_0_4::Board<'_, T> {
+        fw_0_4::board(self)
+    }
+
+    /// One of the axes of the object model.
+    pub fn axis(&mut self, axis: AxisID) -> fw_0_4::Axis<'_, T> {
+        fw_0_4::axis(self, axis)
+    }
+}
@@ -1,10 +1,18 @@
-use std::io::{Error, Read, Write};
+use std::collections::VecDeque;
+use std::io::{Error, ErrorKind, Read, Write};
 
 #[derive(Eq, PartialEq, Ord, PartialOrd, Default, Debug, Clone)]
 pub struct MockStream {
     pub read_buffer: Vec<u8>,
     pub write_buffer: Vec<u8>,
     pub flushed: bool,
+    /// When set, every read fails with this kind of error.
+    pub read_error: Option<ErrorKind>,
+    /// When set, every write fails with this kind of error.
+    pub write_error: Option<ErrorKind>,
+    /// Replies which become readable one at a time, each time a line is written.
+    /// An empty reply simulates a command which the device does not answer.
+    pub replies: VecDeque<Vec<u8>>,
 }
 
 impl MockStream {
@@ -13,14 +21,28 @@ impl MockStream {
             read_buffer: Vec::new(),
             write_buffer: Vec::new(),
             flushed: false,
+            read_error: None,
+            write_error: None,
+            replies: VecDeque::new(),
         }
     }
 }
 
 impl Write for MockStream {
     fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        if let Some(kind) = self.write_error {
+            return Err(Error::from(kind));
+        }
+
         for e in buf {
             self.write_buffer.push(*e);
+            if *e == b'\n' {
+                if let Some(reply) = self.replies.pop_front() {
+                    for byte in reply {
+                        self.read_buffer.insert(0, byte);
+                    }
+                }
+            }
         }
         Ok(buf.len())
     }
@@ -33,6 +55,10 @@ impl Write for MockStream {
 
 impl Read for MockStream {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        if let Some(kind) = self.read_error {
+            return Err(Error::from(kind));
+        }
+
         let mut count = 0;
         while count < buf.len() {
             if let Some(res) = self.read_buffer.pop() {